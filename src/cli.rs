@@ -6,6 +6,7 @@ use clap::Parser;
 use clap_verbosity_flag::{InfoLevel, Verbosity};
 
 use crate::config::{ColorMode, DisplayMode};
+use crate::status::Status;
 
 const HELP: &str = "\
 More information: https://github.com/nickgerace/gfold
@@ -24,6 +25,18 @@ Config file usage: while CLI options are prioritized, default options will fallb
 pub struct Cli {
     /// Specify path(s) to target directories (defaults to current working directory)
     pub paths: Option<Vec<PathBuf>>,
+    /// Compare all local branches against their remote branches (not only the current branch)
+    #[arg(long)]
+    pub all_branches: bool,
+    /// Traverse a hidden directory with the given name (e.g. ".config")
+    #[arg(long, value_name = "NAME")]
+    pub allow_hidden: Option<Vec<String>>,
+    /// Exit with a non-zero code describing the statuses found if any repository has a failing status
+    #[arg(long)]
+    pub check: bool,
+    /// Configure which statuses are failing statuses for "--check" (defaults to "unclean,unpushed,behind,diverged,error")
+    #[arg(long, value_name = "STATUS", value_delimiter = ',')]
+    pub check_status: Option<Vec<Status>>,
     /// Configure the color settings
     #[arg(short, long)]
     pub color_mode: Option<ColorMode>,
@@ -33,12 +46,45 @@ pub struct Cli {
     /// Display finalized config options and exit (merged options from an optional config file and command line arguments)
     #[arg(long)]
     pub dry_run: bool,
+    /// Do not display repositories with the given status(es) (e.g. "clean,bare")
+    #[arg(long, value_name = "STATUS", value_delimiter = ',')]
+    pub exclude_status: Option<Vec<Status>>,
+    /// Follow symbolic links to directories during traversal
+    #[arg(long)]
+    pub follow_symlinks: bool,
+    /// Display each result using a template instead of the display mode (e.g. "{status_color}{status}{reset} {path} ({branch})")
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format: Option<String>,
     /// Generate a man page for gfold
     #[arg(long)]
     pub generate_man: bool,
+    /// Skip directories matching a gitignore-style pattern (in addition to patterns from the config file and ".gfoldignore" files)
+    #[arg(long, value_name = "PATTERN")]
+    pub ignore: Option<Vec<String>>,
     /// Ignore config file settings
     #[arg(short, long)]
     pub ignore_config_file: bool,
+    /// Traverse hidden directories (i.e. directories whose names start with ".")
+    #[arg(long)]
+    pub include_hidden: bool,
+    /// Traverse the working trees of repositories to find repositories nested inside them
+    #[arg(long)]
+    pub include_nested: bool,
+    /// Stop traversing after the given number of levels below each target directory
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+    /// Terminate porcelain records with NUL rather than newline characters
+    #[arg(short = 'z', long = "null")]
+    pub null_terminated: bool,
+    /// Do not traverse directories on other file systems (like "find -xdev")
+    #[arg(long)]
+    pub one_file_system: bool,
+    /// Only display repositories with the given status(es) (e.g. "unclean,unpushed")
+    #[arg(long, value_name = "STATUS", value_delimiter = ',')]
+    pub status: Option<Vec<Status>>,
+    /// Only display the number of repositories found for each status
+    #[arg(long)]
+    pub summary_only: bool,
     /// Specify verbosity levels for runtime execution
     #[command(flatten)]
     pub verbose: Verbosity<InfoLevel>,
@@ -1,12 +1,14 @@
 //! This module contains the functionality for generating reports.
 
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Result;
+use log::debug;
 use rayon::prelude::*;
 use target::TargetCollector;
 
+use crate::config::Config;
 use crate::repository_view::RepositoryView;
 
 mod target;
@@ -21,15 +23,62 @@ pub type RepositoryCollection = BTreeMap<Option<String>, Vec<RepositoryView>>;
 
 type UnprocessedRepositoryView = Result<RepositoryView>;
 
-/// Generate [`RepositoryCollection`] for a given path and its children.
+/// Generate a single [`RepositoryCollection`] for the given paths and their children. Targets
+/// found via multiple paths (e.g. overlapping paths) are only included once. Repositories that could
+/// not be processed are included with the [`Error`](crate::status::Status::Error) status rather
+/// than aborting collection altogether.
 pub fn run(
-    path: &Path,
+    paths: &[PathBuf],
+    config: &Config,
     include_email: bool,
     include_submodules: bool,
 ) -> Result<RepositoryCollection> {
-    let unprocessed = TargetCollector::run(path.to_path_buf())?
+    run_streaming(paths, config, include_email, include_submodules, |_| Ok(()))
+}
+
+/// Generate a single [`RepositoryCollection`] in the same manner as [`run`], but also provide each
+/// [`RepositoryView`] to the given function as soon as it has been generated. Views are generated
+/// concurrently, so the order in which they are provided is not guaranteed.
+pub fn run_streaming<F>(
+    paths: &[PathBuf],
+    config: &Config,
+    include_email: bool,
+    include_submodules: bool,
+    on_view: F,
+) -> Result<RepositoryCollection>
+where
+    F: Fn(&RepositoryView) -> Result<()> + Sync,
+{
+    let target_collector = TargetCollector::new(config);
+    let mut targets = Vec::new();
+    for path in paths {
+        debug!("processing path: {}", path.display());
+        targets.extend(target_collector.run(path.to_path_buf())?);
+    }
+    targets.sort();
+    targets.dedup();
+    let containing_targets = find_containing_targets(&targets);
+
+    let unprocessed = targets
         .par_iter()
-        .map(|path| RepositoryView::new(path, include_email, include_submodules))
+        .zip(containing_targets.par_iter())
+        .map(|(path, containing_target)| {
+            let mut view = match RepositoryView::new(
+                path,
+                include_email,
+                include_submodules,
+                config.all_branches,
+            ) {
+                Ok(view) => view,
+                Err(e) => {
+                    debug!("could not process repository: {}: {e}", path.display());
+                    RepositoryView::from_error(path, &e)?
+                }
+            };
+            view.nested_in = containing_target.and_then(|p| p.to_str().map(|s| s.to_string()));
+            on_view(&view)?;
+            Ok(view)
+        })
         .collect::<Vec<UnprocessedRepositoryView>>();
 
     let mut processed = RepositoryCollection::new();
@@ -42,3 +91,41 @@ pub fn run(
     }
     Ok(processed)
 }
+
+/// Create a new [`RepositoryCollection`] containing only the [`RepositoryViews`](RepositoryView)
+/// that match the predicate. Groups left without any views are not included.
+pub fn filter<P>(collection: &RepositoryCollection, predicate: P) -> RepositoryCollection
+where
+    P: Fn(&RepositoryView) -> bool,
+{
+    collection
+        .iter()
+        .filter_map(|(parent, views)| {
+            let views = views
+                .iter()
+                .filter(|view| predicate(view))
+                .cloned()
+                .collect::<Vec<RepositoryView>>();
+            (!views.is_empty()).then(|| (parent.clone(), views))
+        })
+        .collect()
+}
+
+/// Find the closest target containing each target, which is only possible when nested
+/// repositories are included during traversal. The targets must be sorted, which ensures that
+/// each target is immediately followed by the targets nested inside it.
+fn find_containing_targets(targets: &[PathBuf]) -> Vec<Option<&PathBuf>> {
+    let mut ancestors: Vec<&PathBuf> = Vec::new();
+    let mut containing_targets = Vec::with_capacity(targets.len());
+    for target in targets {
+        while let Some(ancestor) = ancestors.last() {
+            if target.starts_with(ancestor) && target != *ancestor {
+                break;
+            }
+            ancestors.pop();
+        }
+        containing_targets.push(ancestors.last().copied());
+        ancestors.push(target);
+    }
+    containing_targets
+}
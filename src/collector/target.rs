@@ -1,22 +1,115 @@
 //! This module contains target generation logic required for generating
 //! [`RepositoryViews`](crate::repository_view::RepositoryView).
 
+use anyhow::Result;
+use git2::{Config, Repository, RepositoryOpenFlags};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use log::{debug, error, warn};
 use rayon::prelude::*;
+use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::fs::DirEntry;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::{fs, io};
 
+use crate::config;
+
+/// The name of the file containing gitignore-style patterns for directories that will not be
+/// traversed. The patterns apply to the directory containing the file and its children.
+const IGNORE_FILE_NAME: &str = ".gfoldignore";
+
 /// An unprocessed target that needs to be disassembled before consumption.
-type UnprocessedTarget = io::Result<MaybeTarget>;
+type UnprocessedTarget = Result<MaybeTarget>;
 
-/// A unit struct used to centralizing target collection method(s).
-pub(crate) struct TargetCollector;
+/// A key that uniquely identifies a directory regardless of the path used to reach it. On Unix
+/// platforms, this is the device and inode pair for the directory.
+#[cfg(unix)]
+type VisitKey = (u64, u64);
+
+/// A key that uniquely identifies a directory regardless of the path used to reach it. On non-Unix
+/// platforms, this is the canonical path for the directory.
+#[cfg(not(unix))]
+type VisitKey = PathBuf;
+
+/// A struct used to centralize target collection method(s) and the options that drive them.
+pub(crate) struct TargetCollector {
+    ignore_patterns: Vec<String>,
+    max_depth: Option<usize>,
+    include_hidden: bool,
+    allow_hidden: Vec<String>,
+    include_nested: bool,
+    follow_symlinks: bool,
+    one_file_system: bool,
+    visited: Mutex<HashMap<VisitKey, usize>>,
+}
 
 impl TargetCollector {
-    /// Generate targets for a given [`PathBuf`] based on its children (recursively). We use
+    /// Creates a new target collector using the traversal options from the
+    /// [`Config`](config::Config).
+    pub(crate) fn new(config: &config::Config) -> Self {
+        Self {
+            ignore_patterns: config.ignore.clone(),
+            max_depth: config.max_depth,
+            include_hidden: config.include_hidden,
+            allow_hidden: config.allow_hidden.clone(),
+            include_nested: config.include_nested,
+            follow_symlinks: config.follow_symlinks,
+            one_file_system: config.one_file_system,
+            visited: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Generate targets for a given [`PathBuf`] based on its children (recursively). Ignore
+    /// patterns provided via the [`Config`](config::Config) are anchored to the given
+    /// [`PathBuf`]. If symbolic links are followed, targets are canonicalized and de-duplicated.
+    pub(crate) fn run(&self, path: PathBuf) -> Result<Vec<PathBuf>> {
+        let mut builder = GitignoreBuilder::new(&path);
+        for pattern in &self.ignore_patterns {
+            builder.add_line(None, pattern)?;
+        }
+        let traversal = Traversal {
+            depth: 0,
+            ignores: vec![Arc::new(builder.build()?)],
+            submodules: Arc::new([]),
+            device: match self.one_file_system {
+                true => Self::find_device(&path),
+                false => None,
+            },
+        };
+        if !self.follow_symlinks {
+            return self.collect(path, &traversal);
+        }
+
+        self.visit(&path, traversal.depth);
+        let mut targets = self
+            .collect(path, &traversal)?
+            .into_iter()
+            .map(|target| match fs::canonicalize(&target) {
+                Ok(canonical) => canonical,
+                Err(e) => {
+                    debug!("{}: {}", e, target.display());
+                    target
+                }
+            })
+            .collect::<Vec<PathBuf>>();
+        targets.sort();
+        targets.dedup();
+        Ok(targets)
+    }
+
+    /// Collect targets for a given [`PathBuf`] based on its children (recursively). We use
     /// recursion paired with [`rayon`] since we prioritize speed over memory use.
-    pub(crate) fn run(path: PathBuf) -> io::Result<Vec<PathBuf>> {
+    fn collect(&self, path: PathBuf, traversal: &Traversal) -> Result<Vec<PathBuf>> {
+        if self
+            .max_depth
+            .is_some_and(|max_depth| traversal.depth >= max_depth)
+        {
+            debug!("skipping children at max depth: {:?}", path.display());
+            return Ok(Vec::with_capacity(0));
+        }
+
         let entries: Vec<DirEntry> = match fs::read_dir(&path) {
             Ok(read_dir) => read_dir.filter_map(|r| r.ok()).collect(),
             Err(e) => {
@@ -28,9 +121,17 @@ impl TargetCollector {
             }
         };
 
+        // Patterns from an ignore file apply to the current directory and take precedence over
+        // patterns from its parents.
+        let mut children = traversal.clone();
+        children.depth += 1;
+        if let Some(ignore) = Self::read_ignore_file(&path) {
+            children.ignores.push(Arc::new(ignore));
+        }
+
         let unprocessed = entries
             .par_iter()
-            .map(Self::determine_target)
+            .map(|entry| self.determine_target(entry, &children))
             .collect::<Vec<UnprocessedTarget>>();
 
         let mut results = Vec::new();
@@ -45,38 +146,326 @@ impl TargetCollector {
         Ok(results)
     }
 
-    /// Ensure the entry is a directory and is not hidden. Then, check if a ".git" sub directory
-    /// exists, which will indicate if the entry is a repository. If the directory is not a Git
-    /// repository, then we will recursively call [`Self::run()`].
-    fn determine_target(entry: &DirEntry) -> io::Result<MaybeTarget> {
-        if entry.file_type()?.is_dir()
-            && !entry
-                .file_name()
-                .to_str()
-                .is_some_and(|file_name| file_name.starts_with('.'))
-        {
+    /// Ensure the entry is a directory, is not a skipped hidden directory and is not ignored. Then,
+    /// check if a ".git" sub item exists or if the entry is a bare repository, either of which will
+    /// indicate if the entry is a repository. If the directory is not a Git repository, then we will
+    /// recursively call [`Self::collect()`].
+    fn determine_target(&self, entry: &DirEntry, traversal: &Traversal) -> Result<MaybeTarget> {
+        if self.is_directory(entry)? {
             let path = entry.path();
+            if self.is_skipped_hidden(entry) || Self::is_ignored(&path, &traversal.ignores) {
+                return Ok(MaybeTarget::None);
+            }
+            if traversal.submodules.contains(&path) {
+                debug!("skipping submodule: {:?}", &path.display());
+                return Ok(MaybeTarget::None);
+            }
+            if let Some(device) = traversal.device
+                && Self::find_device(&path).is_some_and(|found| found != device)
+            {
+                debug!(
+                    "skipping directory on another file system: {:?}",
+                    &path.display()
+                );
+                return Ok(MaybeTarget::None);
+            }
+            if self.follow_symlinks && !self.visit(&path, traversal.depth) {
+                debug!(
+                    "skipping previously visited directory: {:?}",
+                    &path.display()
+                );
+                return Ok(MaybeTarget::None);
+            }
+
             let git_sub_item = path.join(".git");
             if git_sub_item.exists() {
                 if git_sub_item.is_dir() {
                     debug!("found target: {:?}", &path.display());
-                    return Ok(MaybeTarget::Single(path));
+                    return self.with_nested_targets(path, traversal);
                 } else if git_sub_item.is_file() {
-                    debug!("found a worktree: {:?}", &path.display());
+                    Self::log_gitfile(&path, &git_sub_item);
+                    return self.with_nested_targets(path, traversal);
+                }
+            }
+            // Bare repositories are targets in their own right. Other Git directories are reported
+            // via their working trees. In either case, we avoid traversing their object stores.
+            if Self::is_git_directory(&path) {
+                if Self::is_bare_repository(&path) {
+                    debug!("found a bare repository: {:?}", &path.display());
                     return Ok(MaybeTarget::Single(path));
                 }
+                debug!("skipping non-bare git directory: {:?}", &path.display());
+                return Ok(MaybeTarget::None);
             }
-            Ok(MaybeTarget::Multiple(Self::run(path)?))
+            Ok(MaybeTarget::Multiple(self.collect(path, traversal)?))
         } else {
             Ok(MaybeTarget::None)
         }
     }
+
+    /// Check if an entry is a directory. Symbolic links to directories are only considered
+    /// directories if symbolic links are followed.
+    fn is_directory(&self, entry: &DirEntry) -> io::Result<bool> {
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() && self.follow_symlinks {
+            let path = entry.path();
+            let is_dir = path.is_dir();
+            if is_dir {
+                debug!("following symlink: {:?}", path.display());
+            }
+            return Ok(is_dir);
+        }
+        Ok(file_type.is_dir())
+    }
+
+    /// Mark a directory as visited at the given depth. Returns `false` if the directory was already
+    /// visited via another path at the same depth or shallower, which prevents both cycles and
+    /// duplicate targets when following symbolic links. A directory reached at a shallower depth is
+    /// visited again since the maximum depth may have prevented its children from being read. If
+    /// the directory cannot be identified, it is treated as unvisited.
+    fn visit(&self, path: &Path, depth: usize) -> bool {
+        let key = match Self::visit_key(path) {
+            Ok(key) => key,
+            Err(e) => {
+                debug!("{}: {}", e, path.display());
+                return true;
+            }
+        };
+        let mut visited = self.visited.lock().unwrap_or_else(PoisonError::into_inner);
+        match visited.get(&key) {
+            Some(&shallowest) if shallowest <= depth => false,
+            _ => {
+                visited.insert(key, depth);
+                true
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn visit_key(path: &Path) -> io::Result<VisitKey> {
+        use std::os::unix::fs::MetadataExt;
+        let metadata = fs::metadata(path)?;
+        Ok((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(not(unix))]
+    fn visit_key(path: &Path) -> io::Result<VisitKey> {
+        fs::canonicalize(path)
+    }
+
+    /// Find the device that a directory resides on. Absorb and log any and all errors since the
+    /// directory will be traversed as if staying on one file system was not requested.
+    #[cfg(unix)]
+    fn find_device(path: &Path) -> Option<u64> {
+        use std::os::unix::fs::MetadataExt;
+        match fs::metadata(path) {
+            Ok(metadata) => Some(metadata.dev()),
+            Err(e) => {
+                debug!("{}: {}", e, path.display());
+                None
+            }
+        }
+    }
+
+    /// Find the device that a directory resides on. Staying on one file system is only supported
+    /// on Unix platforms.
+    #[cfg(not(unix))]
+    fn find_device(_: &Path) -> Option<u64> {
+        warn!("staying on one file system is unsupported on this platform");
+        None
+    }
+
+    /// Returns the target for a repository with a working tree. If nested repositories are
+    /// included, the working tree is traversed as well, skipping the repository's registered
+    /// submodules.
+    fn with_nested_targets(&self, path: PathBuf, traversal: &Traversal) -> Result<MaybeTarget> {
+        if !self.include_nested {
+            return Ok(MaybeTarget::Single(path));
+        }
+        let mut nested = traversal.clone();
+        nested.submodules = Self::find_submodule_paths(&path).into();
+        let mut targets = self.collect(path.clone(), &nested)?;
+        targets.push(path);
+        Ok(MaybeTarget::Multiple(targets))
+    }
+
+    /// Find the paths of submodules registered in a repository's ".gitmodules" file. Absorb and
+    /// log any and all errors since the file is only used to skip submodules during traversal.
+    fn find_submodule_paths(path: &Path) -> Vec<PathBuf> {
+        let gitmodules = path.join(".gitmodules");
+        if !gitmodules.is_file() {
+            return Vec::with_capacity(0);
+        }
+        let config = match Config::open(&gitmodules) {
+            Ok(config) => config,
+            Err(e) => {
+                debug!("ignored error: {e}");
+                return Vec::with_capacity(0);
+            }
+        };
+        let mut entries = match config.entries(Some(r"submodule\..*\.path")) {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!("ignored error: {e}");
+                return Vec::with_capacity(0);
+            }
+        };
+
+        let mut submodules = Vec::new();
+        while let Some(entry) = entries.next() {
+            match entry {
+                Ok(entry) => {
+                    if let Some(value) = entry.value() {
+                        submodules.push(path.join(value));
+                    }
+                }
+                Err(e) => debug!("ignored error: {e}"),
+            }
+        }
+        submodules
+    }
+
+    /// Check if a directory has the layout that Git requires of a Git directory. Both bare
+    /// repositories and Git directories separated from their working trees have this layout.
+    fn is_git_directory(path: &Path) -> bool {
+        path.join("HEAD").is_file() && path.join("objects").is_dir() && path.join("refs").is_dir()
+    }
+
+    /// Check if a Git directory belongs to a bare repository by opening it.
+    fn is_bare_repository(path: &Path) -> bool {
+        match Repository::open_ext(path, RepositoryOpenFlags::NO_SEARCH, &[] as &[&OsStr]) {
+            Ok(repository) => repository.is_bare(),
+            Err(e) => {
+                debug!("{}: {}", e, path.display());
+                false
+            }
+        }
+    }
+
+    /// Log where a ".git" file (i.e. a "gitfile") redirects to. Worktrees, submodules and
+    /// repositories initialized with a separate Git directory all use gitfiles. If the redirection
+    /// is broken, the target is still returned so that the error can be reported alongside other
+    /// results.
+    fn log_gitfile(path: &Path, gitfile: &Path) {
+        let contents = match fs::read_to_string(gitfile) {
+            Ok(contents) => contents,
+            Err(e) => {
+                debug!("{}: {}", e, gitfile.display());
+                return;
+            }
+        };
+        match contents.trim_end().strip_prefix("gitdir:") {
+            Some(git_dir) => {
+                let git_dir = path.join(git_dir.trim());
+                if git_dir.is_dir() {
+                    debug!(
+                        "found a gitfile redirection: {:?} -> {:?}",
+                        path.display(),
+                        git_dir.display()
+                    );
+                } else {
+                    debug!(
+                        "found a gitfile redirection to a missing directory: {:?} -> {:?}",
+                        path.display(),
+                        git_dir.display()
+                    );
+                }
+            }
+            None => debug!("found a malformed gitfile: {:?}", gitfile.display()),
+        }
+    }
+
+    /// Check if a directory is hidden and should be skipped. Hidden directories are only entered
+    /// if all hidden directories are included or if the directory name is explicitly allowed. The
+    /// ".git" directory is always skipped.
+    fn is_skipped_hidden(&self, entry: &DirEntry) -> bool {
+        let file_name = entry.file_name();
+        let Some(file_name) = file_name.to_str() else {
+            return false;
+        };
+        if !file_name.starts_with('.') {
+            return false;
+        }
+
+        if file_name == ".git" {
+            debug!("skipping git directory: {:?}", entry.path().display());
+            true
+        } else if self.allow_hidden.iter().any(|allowed| allowed == file_name) {
+            debug!(
+                "entering allowed hidden directory: {:?}",
+                entry.path().display()
+            );
+            false
+        } else if self.include_hidden {
+            false
+        } else {
+            debug!("skipping hidden directory: {:?}", entry.path().display());
+            true
+        }
+    }
+
+    /// Check if a directory matches any ignore pattern. Patterns closest to the directory are
+    /// checked first, which allows for negated patterns to override patterns from parents.
+    fn is_ignored(path: &Path, ignores: &[Arc<Gitignore>]) -> bool {
+        for ignore in ignores.iter().rev() {
+            match ignore.matched(path, true) {
+                Match::Ignore(glob) => {
+                    debug!(
+                        "skipping ignored directory: {:?} (pattern: {})",
+                        path.display(),
+                        glob.original()
+                    );
+                    return true;
+                }
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    /// Read and parse the ignore file within a directory, if it exists. Absorb and log any and all
+    /// errors since ignore files are non-critical to the final results.
+    fn read_ignore_file(path: &Path) -> Option<Gitignore> {
+        let ignore_file = path.join(IGNORE_FILE_NAME);
+        if !ignore_file.is_file() {
+            return None;
+        }
+        debug!("found ignore file: {:?}", ignore_file.display());
+
+        let mut builder = GitignoreBuilder::new(path);
+        if let Some(e) = builder.add(&ignore_file) {
+            warn!("{}: {}", e, ignore_file.display());
+        }
+        match builder.build() {
+            Ok(ignore) => Some(ignore),
+            Err(e) => {
+                warn!("{}: {}", e, ignore_file.display());
+                None
+            }
+        }
+    }
+}
+
+/// The state that a directory passes down to its children during traversal.
+#[derive(Clone)]
+struct Traversal {
+    /// The number of levels that the directory is below the path provided to
+    /// [`TargetCollector::run()`].
+    depth: usize,
+    /// The ignore matchers for the directory, ordered from the farthest to the closest.
+    ignores: Vec<Arc<Gitignore>>,
+    /// The paths of registered submodules within the closest containing repository, if any.
+    submodules: Arc<[PathBuf]>,
+    /// The device of the path provided to [`TargetCollector::run()`] if traversal is restricted to
+    /// one file system.
+    device: Option<u64>,
 }
 
 /// An enum that contains 0 to N targets based on the variant.
 #[remain::sorted]
 enum MaybeTarget {
-    /// Contains multiple targets from recursive call(s) of [`TargetCollector::run()`].
+    /// Contains multiple targets from recursive call(s) of [`TargetCollector::collect()`].
     Multiple(Vec<PathBuf>),
     /// Does not contain a target.
     None,
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::{env, fs};
 
+use crate::status::Status;
+
 /// The configuration for driving `gfold`.
 #[derive(Debug, Serialize)]
 pub struct Config {
@@ -15,6 +17,50 @@ pub struct Config {
     pub display_mode: DisplayMode,
     /// The color mode for results printed to `stdout`.
     pub color_mode: ColorMode,
+    /// The template used to display each result printed to `stdout` (e.g. "{name} {status}"). The
+    /// value will be `None` if results are displayed according to `display_mode`.
+    pub format: Option<String>,
+    /// The statuses of repositories that will be displayed. All statuses will be displayed if
+    /// empty.
+    pub status: Vec<Status>,
+    /// The statuses of repositories that will not be displayed. This takes precedence over
+    /// `status`.
+    pub exclude_status: Vec<Status>,
+    /// Whether or not all local branches will be compared against their remote branches, rather
+    /// than only the current branch.
+    pub all_branches: bool,
+    /// Whether or not only the summary of the number of repositories found for each status will be
+    /// displayed (i.e. without the repositories themselves).
+    pub summary_only: bool,
+    /// Whether or not the exit code will reflect the statuses of repositories found (i.e. whether
+    /// or not any repository has a status in `check_status`).
+    pub check: bool,
+    /// The statuses of repositories that result in a failing exit code when `check` is enabled.
+    pub check_status: Vec<Status>,
+    /// The gitignore-style patterns for directories that will not be traversed. Patterns are
+    /// anchored to each path in `paths`.
+    pub ignore: Vec<String>,
+    /// The maximum number of levels below each path in `paths` that `gfold` will traverse. The
+    /// value will be `None` if traversal depth is unbounded.
+    pub max_depth: Option<usize>,
+    /// Whether or not hidden directories (i.e. directories whose names start with ".") will be
+    /// traversed.
+    pub include_hidden: bool,
+    /// The names of hidden directories that will be traversed even if `include_hidden` is
+    /// disabled (e.g. ".config").
+    pub allow_hidden: Vec<String>,
+    /// Whether or not the working trees of repositories will be traversed in order to find
+    /// repositories nested inside them (excluding registered submodules).
+    pub include_nested: bool,
+    /// Whether or not symbolic links to directories will be followed during traversal. Each
+    /// directory is only traversed once, regardless of how many links point to it.
+    pub follow_symlinks: bool,
+    /// Whether or not traversal will stay on the file system of each path in `paths` (i.e. not
+    /// descend into directories on other file systems, such as network mounts).
+    pub one_file_system: bool,
+    /// Whether or not records will be terminated with NUL rather than newline characters when
+    /// using the porcelain display mode.
+    pub null_terminated: bool,
 }
 
 impl Config {
@@ -59,6 +105,13 @@ impl Config {
         Ok(())
     }
 
+    /// Checks whether or not repositories with the given [`Status`] will be displayed based on the
+    /// `status` and `exclude_status` fields.
+    pub fn displays_status(&self, status: Status) -> bool {
+        (self.status.is_empty() || self.status.contains(&status))
+            && !self.exclude_status.contains(&status)
+    }
+
     fn from_entry_config(entry_config: &EntryConfig) -> Result<Self> {
         if entry_config.path.is_some() && entry_config.paths.is_some() {
             bail!("Cannot have both `path` and `paths` in config");
@@ -85,6 +138,42 @@ impl Config {
                 Some(color_mode) => *color_mode,
                 None => ColorMode::Always,
             },
+            format: entry_config.format.clone(),
+            status: match &entry_config.status {
+                Some(status) => status.clone(),
+                None => Vec::new(),
+            },
+            exclude_status: match &entry_config.exclude_status {
+                Some(exclude_status) => exclude_status.clone(),
+                None => Vec::new(),
+            },
+            all_branches: entry_config.all_branches.unwrap_or_default(),
+            summary_only: entry_config.summary_only.unwrap_or_default(),
+            check: entry_config.check.unwrap_or_default(),
+            check_status: match &entry_config.check_status {
+                Some(check_status) => check_status.clone(),
+                None => vec![
+                    Status::Unclean,
+                    Status::Unpushed,
+                    Status::Behind,
+                    Status::Diverged,
+                    Status::Error,
+                ],
+            },
+            ignore: match &entry_config.ignore {
+                Some(ignore) => ignore.clone(),
+                None => Vec::new(),
+            },
+            max_depth: entry_config.max_depth,
+            include_hidden: entry_config.include_hidden.unwrap_or_default(),
+            allow_hidden: match &entry_config.allow_hidden {
+                Some(allow_hidden) => allow_hidden.clone(),
+                None => Vec::new(),
+            },
+            include_nested: entry_config.include_nested.unwrap_or_default(),
+            follow_symlinks: entry_config.follow_symlinks.unwrap_or_default(),
+            one_file_system: entry_config.one_file_system.unwrap_or_default(),
+            null_terminated: entry_config.null_terminated.unwrap_or_default(),
         })
     }
 }
@@ -116,6 +205,36 @@ struct EntryConfig {
     pub display_mode: Option<DisplayMode>,
     /// Reflection of the `color_mode` field on [`Config`].
     pub color_mode: Option<ColorMode>,
+    /// Reflection of the `format` field on [`Config`].
+    pub format: Option<String>,
+    /// Reflection of the `status` field on [`Config`].
+    pub status: Option<Vec<Status>>,
+    /// Reflection of the `exclude_status` field on [`Config`].
+    pub exclude_status: Option<Vec<Status>>,
+    /// Reflection of the `all_branches` field on [`Config`].
+    pub all_branches: Option<bool>,
+    /// Reflection of the `summary_only` field on [`Config`].
+    pub summary_only: Option<bool>,
+    /// Reflection of the `check` field on [`Config`].
+    pub check: Option<bool>,
+    /// Reflection of the `check_status` field on [`Config`].
+    pub check_status: Option<Vec<Status>>,
+    /// Reflection of the `ignore` field on [`Config`].
+    pub ignore: Option<Vec<String>>,
+    /// Reflection of the `max_depth` field on [`Config`].
+    pub max_depth: Option<usize>,
+    /// Reflection of the `include_hidden` field on [`Config`].
+    pub include_hidden: Option<bool>,
+    /// Reflection of the `allow_hidden` field on [`Config`].
+    pub allow_hidden: Option<Vec<String>>,
+    /// Reflection of the `include_nested` field on [`Config`].
+    pub include_nested: Option<bool>,
+    /// Reflection of the `follow_symlinks` field on [`Config`].
+    pub follow_symlinks: Option<bool>,
+    /// Reflection of the `one_file_system` field on [`Config`].
+    pub one_file_system: Option<bool>,
+    /// Reflection of the `null_terminated` field on [`Config`].
+    pub null_terminated: Option<bool>,
 }
 
 /// Dictates how the results gathered should be displayed to the user via `stdout`. Setting this
@@ -130,14 +249,30 @@ struct EntryConfig {
 pub enum DisplayMode {
     /// Informs the caller to display results in the classic format.
     Classic,
-    /// Informs the caller to display results in JSON format.
+    /// Informs the caller to display results in CSV format with a header row. All results are
+    /// sorted alphabetically and then sorted by status.
+    Csv,
+    /// Informs the caller to display results in JSON format as a flat array of repositories.
     Json,
+    /// Informs the caller to display results as a single JSON document, which contains metadata
+    /// (the `gfold` version, the paths traversed and a timestamp) alongside the repositories.
+    JsonDocument,
+    /// Informs the caller to display results in newline-delimited JSON format (i.e. one compact
+    /// JSON object per line). Results are displayed as soon as they are collected and are not
+    /// sorted.
+    Ndjson,
+    /// Informs the caller to display results in a stable, line-oriented format intended for
+    /// scripts (see the [`display`](crate::display) module for the versioned field order).
+    Porcelain,
     /// Informs the caller to display results in the standard (default) format. All results are
     /// sorted alphabetically and then sorted by status.
     Standard,
     /// Informs the caller to display results in the standard (default) format with a twist: all
     /// results are solely sorted alphabetically (i.e. no additional sort by status).
     StandardAlphabetical,
+    /// Informs the caller to display results in TSV format with a header row. All results are
+    /// sorted alphabetically and then sorted by status.
+    Tsv,
 }
 
 /// Set the color mode of results printed to `stdout`.
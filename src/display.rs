@@ -1,33 +1,139 @@
 //! This module contains the functionality for displaying reports to `stdout`.
 
-use std::io;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow};
 use color::ColorHarness;
+use jiff::Timestamp;
 use log::debug;
 use log::warn;
+use serde::Serialize;
+use template::Template;
 
 use crate::collector::RepositoryCollection;
-use crate::config::{ColorMode, DisplayMode};
+use crate::config::{ColorMode, Config, DisplayMode};
+use crate::repository_view::{BranchView, RepositoryView};
 
 // TODO(nick): make this module private.
 pub mod color;
+mod template;
 
 const PAD: usize = 2;
 const NONE: &str = "none";
+const PORCELAIN_HEADER: &str = "# gfold porcelain v1";
+const PORCELAIN_EMPTY: &str = "-";
+const DELIMITED_HEADER: [&str; 10] = [
+    "name",
+    "path",
+    "branch",
+    "status",
+    "url",
+    "email",
+    "ahead",
+    "behind",
+    "nested_in",
+    "error",
+];
 
-/// This function chooses the display execution function based on the [`DisplayMode`] and [`ColorMode`] provided.
-pub fn run(
-    display_mode: DisplayMode,
-    color_mode: ColorMode,
-    reports: &RepositoryCollection,
-) -> Result<()> {
-    match display_mode {
+/// The JSON document displayed for an entire run, which contains metadata alongside the
+/// [`RepositoryView`] list.
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    /// The version of `gfold` that generated the document.
+    version: &'static str,
+    /// The paths that `gfold` traversed.
+    roots: &'a [PathBuf],
+    /// The time at which the document was generated in RFC 3339 format.
+    timestamp: String,
+    /// The number of repositories found for each status.
+    summary: Summary,
+    /// The views for all repositories found.
+    repositories: Vec<RepositoryView>,
+}
+
+/// The number of repositories found in total and for each status (e.g. "42 repos: 35 clean,
+/// 4 unclean, 2 unpushed, 1 bare").
+#[derive(Serialize)]
+struct Summary {
+    /// The total number of repositories found.
+    total: usize,
+    /// The number of repositories found for each status. Statuses without any repositories are
+    /// not included.
+    statuses: BTreeMap<&'static str, usize>,
+}
+
+impl Summary {
+    fn new(reports: &RepositoryCollection) -> Self {
+        let mut statuses = BTreeMap::new();
+        for report in reports.values().flatten() {
+            *statuses.entry(report.status.as_str()).or_default() += 1;
+        }
+        Self {
+            total: statuses.values().sum(),
+            statuses,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            self.total,
+            if self.total == 1 { "repo" } else { "repos" }
+        )?;
+
+        // Display the most common statuses first and use the status name to break ties.
+        let mut statuses = self.statuses.iter().collect::<Vec<_>>();
+        statuses.sort_by(|a, b| b.1.cmp(a.1));
+        for (index, (status, count)) in statuses.into_iter().enumerate() {
+            let separator = if index == 0 { ":" } else { "," };
+            write!(f, "{separator} {count} {status}")?;
+        }
+        Ok(())
+    }
+}
+
+/// This function chooses the display execution function based on the [`DisplayMode`] and [`ColorMode`] provided
+/// via the [`Config`]. If a format template is provided, it is used instead of the [`DisplayMode`].
+/// If only the summary is requested, neither is used.
+pub fn run(config: &Config, reports: &RepositoryCollection) -> Result<()> {
+    let color_mode = config.color_mode;
+    if config.summary_only {
+        return summary(reports, config.display_mode);
+    }
+    if let Some(format) = &config.format {
+        return template(reports, color_mode, format);
+    }
+    match config.display_mode {
         DisplayMode::Standard => standard(reports, color_mode, false)?,
         DisplayMode::StandardAlphabetical => standard(reports, color_mode, true)?,
         DisplayMode::Json => json(reports)?,
+        DisplayMode::JsonDocument => json_document(reports, &config.paths)?,
+        DisplayMode::Ndjson => debug!("detected ndjson display mode: results already displayed"),
+        DisplayMode::Porcelain => porcelain(reports, config.null_terminated)?,
         DisplayMode::Classic => classic(reports, color_mode)?,
+        DisplayMode::Csv => delimited(reports, ',', escape_csv)?,
+        DisplayMode::Tsv => delimited(reports, '\t', escape_tsv)?,
+    }
+    Ok(())
+}
+
+/// Display only the [`Summary`] of the [`RepositoryCollection`] to `stdout`. The summary is
+/// displayed in JSON format for JSON-based display modes.
+fn summary(reports: &RepositoryCollection, display_mode: DisplayMode) -> Result<()> {
+    debug!("detected summary only");
+    let summary = Summary::new(reports);
+    match display_mode {
+        DisplayMode::Json | DisplayMode::JsonDocument => {
+            println!("{}", serde_json::to_string_pretty(&summary)?)
+        }
+        DisplayMode::Ndjson => println!("{}", serde_json::to_string(&summary)?),
+        _ => println!("{summary}"),
     }
     Ok(())
 }
@@ -54,11 +160,11 @@ fn standard(
     for report in all_reports {
         color_harness.write_bold(&report.name, false)?;
 
-        let Some(parent) = report.parent else {
+        if report.parent.is_none() {
             warn!("parent is empty for collector: {}", report.name);
             continue;
-        };
-        let full_path = Path::new(&parent).join(&report.name);
+        }
+        let full_path = full_path(&report);
         let full_path_formatted = format!(
             " ~ {}",
             full_path.to_str().ok_or(anyhow!(
@@ -69,13 +175,43 @@ fn standard(
 
         print!("  ");
         color_harness.write_status(report.status, PAD)?;
-        println!(" ({})", report.branch);
+        print!("{}{}", format_changes(&report), format_stashes(&report));
+        color_harness.write_warning(&format_operation(&report), false)?;
+        println!(" ({})", format_branch(&report));
+        for branch in report
+            .branches
+            .iter()
+            .filter(|b| b.name != report.branch && (b.unpushed > 0 || b.gone_upstream.is_some()))
+        {
+            color_harness.write_gray(&format!("  {}", format_other_branch(branch)), true)?;
+        }
         if let Some(url) = &report.url {
             println!("  {url}");
         }
         if let Some(email) = &report.email {
             println!("  {email}");
         }
+        if let Some(error) = &report.error {
+            println!("  {error}");
+        }
+        if let Some(nested_in) = &report.nested_in {
+            color_harness.write_gray(&format!("  nested in {nested_in}"), true)?;
+        }
+    }
+
+    println!();
+    color_harness.write_bold(&Summary::new(reports).to_string(), true)?;
+    Ok(())
+}
+
+/// Display [`RepositoryCollection`] to `stdout` using a user-defined format template. All results
+/// are sorted alphabetically and then sorted by status.
+fn template(reports: &RepositoryCollection, color_mode: ColorMode, format: &str) -> Result<()> {
+    debug!("detected format template: {format}");
+    let template = Template::parse(format)?;
+    let color_harness = ColorHarness::new(color_mode);
+    for report in sort_by_status(reports) {
+        template.write(&report, &color_harness)?;
     }
     Ok(())
 }
@@ -83,13 +219,34 @@ fn standard(
 /// Display [`RepositoryCollection`] to `stdout` in JSON format.
 fn json(reports: &RepositoryCollection) -> serde_json::Result<()> {
     debug!("detected json display mode");
-    let mut all_reports = Vec::new();
-    for grouped_report in reports {
-        all_reports.append(&mut grouped_report.1.clone());
-    }
-    all_reports.sort_by(|a, b| a.name.cmp(&b.name));
-    all_reports.sort_by(|a, b| a.status.as_str().cmp(b.status.as_str()));
-    println!("{}", serde_json::to_string_pretty(&all_reports)?);
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&sort_by_status(reports))?
+    );
+    Ok(())
+}
+
+/// Display [`RepositoryCollection`] to `stdout` as a single JSON document with metadata.
+fn json_document(reports: &RepositoryCollection, roots: &[PathBuf]) -> serde_json::Result<()> {
+    debug!("detected json document display mode");
+    let document = JsonDocument {
+        version: env!("CARGO_PKG_VERSION"),
+        roots,
+        timestamp: Timestamp::now().to_string(),
+        summary: Summary::new(reports),
+        repositories: sort_by_status(reports),
+    };
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(())
+}
+
+/// Display a single [`RepositoryView`] to `stdout` as one line of newline-delimited JSON. This
+/// function is intended to be called as each view is collected rather than after collection.
+pub fn ndjson(report: &RepositoryView) -> Result<()> {
+    let line = serde_json::to_string(report)?;
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{line}")?;
+    stdout.flush()?;
     Ok(())
 }
 
@@ -124,12 +281,16 @@ fn classic(reports: &RepositoryCollection, color_mode: ColorMode) -> io::Result<
             if report.name.len() > name_max {
                 name_max = report.name.len();
             }
-            let status_length = report.status.as_str().len();
+            let status_length = report.status.as_str().len()
+                + format_changes(report).chars().count()
+                + format_stashes(report).chars().count()
+                + format_operation(report).chars().count();
             if status_length > status_max {
                 status_max = status_length;
             }
-            if report.branch.len() > branch_max {
-                branch_max = report.branch.len();
+            let branch_length = format_branch(report).chars().count();
+            if branch_length > branch_max {
+                branch_max = branch_length;
             }
         }
 
@@ -139,17 +300,446 @@ fn classic(reports: &RepositoryCollection, color_mode: ColorMode) -> io::Result<
 
         for report in reports {
             print!("{:<path_width$}", report.name, path_width = name_max + PAD);
-            color_harness.write_status(report.status, status_max + PAD)?;
-            println!(
+            let changes = format!("{}{}", format_changes(&report), format_stashes(&report));
+            let operation = format_operation(&report);
+            color_harness.write_status(report.status, report.status.as_str().len())?;
+            print!("{changes}");
+            color_harness.write_warning(&operation, false)?;
+            print!(
+                "{:<padding$}",
+                "",
+                padding = status_max + PAD
+                    - report.status.as_str().len()
+                    - changes.chars().count()
+                    - operation.chars().count()
+            );
+            print!(
                 "{:<branch_width$}{}",
-                report.branch,
-                match &report.url {
-                    Some(s) => s,
-                    None => NONE,
+                format_branch(&report),
+                match (&report.error, &report.url) {
+                    (Some(s), _) | (None, Some(s)) => s,
+                    (None, None) => NONE,
                 },
                 branch_width = branch_max + PAD
             );
+            match &report.nested_in {
+                Some(nested_in) => {
+                    color_harness.write_gray(&format!("  nested in {nested_in}"), true)?;
+                }
+                None => println!(),
+            }
         }
     }
+
+    println!();
+    println!("{}", Summary::new(reports));
+    Ok(())
+}
+
+/// Display [`RepositoryCollection`] to `stdout` in the porcelain format, which is intended to be
+/// parsed by scripts. Its contract is versioned by the header record and will not change within
+/// a version.
+///
+/// Version 1 begins with the `# gfold porcelain v1` header record and contains one record per
+/// repository with the following space-separated fields (in order): status, branch, upstream,
+/// ahead, behind, gone upstream, operation, stashes, url, nested in, error and path. If the remote
+/// branch could not be compared against, the upstream is displayed without the ahead and behind
+/// counts. Missing values are displayed as `-` and the path is always the final field, so it may
+/// contain spaces. Records are terminated by newlines or, if `null_terminated` is enabled, by NUL
+/// characters (for paths containing newlines).
+fn porcelain(reports: &RepositoryCollection, null_terminated: bool) -> io::Result<()> {
+    debug!("detected porcelain display mode");
+    let terminator = if null_terminated { '\0' } else { '\n' };
+    let mut stdout = io::stdout().lock();
+    write!(stdout, "{PORCELAIN_HEADER}{terminator}")?;
+
+    for report in sort_by_status(reports) {
+        write!(stdout, "{}{terminator}", format_porcelain(&report))?;
+    }
+    Ok(())
+}
+
+/// Format a single [`RepositoryView`] as a porcelain record without its terminator. Every field
+/// except the path is quoted with [`quote_porcelain`] so that the path can always be found by
+/// taking the remainder of the record after the eleventh separator.
+fn format_porcelain(report: &RepositoryView) -> String {
+    let (upstream, ahead, behind) = match (&report.upstream, &report.incomparable_upstream) {
+        (Some(upstream), _) => (
+            upstream.name.clone(),
+            upstream.ahead.to_string(),
+            upstream.behind.to_string(),
+        ),
+        (None, Some(incomparable_upstream)) => (
+            incomparable_upstream.clone(),
+            PORCELAIN_EMPTY.to_string(),
+            PORCELAIN_EMPTY.to_string(),
+        ),
+        (None, None) => (
+            PORCELAIN_EMPTY.to_string(),
+            PORCELAIN_EMPTY.to_string(),
+            PORCELAIN_EMPTY.to_string(),
+        ),
+    };
+    format!(
+        "{} {} {} {ahead} {behind} {} {} {} {} {} {} {}",
+        report.status.as_str(),
+        quote_porcelain(&report.branch),
+        quote_porcelain(&upstream),
+        quote_porcelain(report.gone_upstream.as_deref().unwrap_or(PORCELAIN_EMPTY)),
+        report
+            .operation
+            .as_ref()
+            .map_or(PORCELAIN_EMPTY, |operation| operation.as_str()),
+        report.stashes.len(),
+        quote_porcelain(report.url.as_deref().unwrap_or(PORCELAIN_EMPTY)),
+        quote_porcelain(report.nested_in.as_deref().unwrap_or(PORCELAIN_EMPTY)),
+        quote_porcelain(report.error.as_deref().unwrap_or(PORCELAIN_EMPTY)),
+        full_path(report).display(),
+    )
+}
+
+/// Quote a porcelain field in the same style that Git uses for unusual paths: if the field
+/// contains a space, a double quote, a backslash or a control character, it is wrapped in double
+/// quotes with those characters backslash-escaped. Other fields are returned unchanged.
+fn quote_porcelain(field: &str) -> String {
+    if !field
+        .chars()
+        .any(|c| c == ' ' || c == '"' || c == '\\' || c.is_control())
+    {
+        return field.to_string();
+    }
+    let mut quoted = String::with_capacity(field.len() + 2);
+    quoted.push('"');
+    for c in field.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\t' => quoted.push_str("\\t"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\{:03o}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Display [`RepositoryCollection`] to `stdout` as delimiter-separated values with a header row.
+/// Every field is passed through the provided escape function before being written.
+fn delimited(
+    reports: &RepositoryCollection,
+    delimiter: char,
+    escape: fn(&str) -> String,
+) -> io::Result<()> {
+    debug!("detected delimited display mode with delimiter: {delimiter:?}");
+    let separator = delimiter.to_string();
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", DELIMITED_HEADER.join(&separator))?;
+
+    for report in sort_by_status(reports) {
+        let path = full_path(&report);
+        let (ahead, behind) = match &report.upstream {
+            Some(upstream) => (upstream.ahead.to_string(), upstream.behind.to_string()),
+            None => (String::new(), String::new()),
+        };
+        let fields = [
+            report.name.as_str(),
+            &path.to_string_lossy(),
+            &report.branch,
+            report.status.as_str(),
+            report.url.as_deref().unwrap_or_default(),
+            report.email.as_deref().unwrap_or_default(),
+            &ahead,
+            &behind,
+            report.nested_in.as_deref().unwrap_or_default(),
+            report.error.as_deref().unwrap_or_default(),
+        ];
+        let row = fields
+            .iter()
+            .map(|field| escape(field))
+            .collect::<Vec<String>>();
+        writeln!(stdout, "{}", row.join(&separator))?;
+    }
     Ok(())
 }
+
+/// Escape a field for CSV output (RFC 4180): fields containing commas, quotes or line breaks are
+/// quoted and quotes within them are doubled.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape a field for TSV output: backslashes, tabs and line breaks are replaced with their
+/// backslash escape sequences since TSV has no quoting mechanism.
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Format the branch name of a [`RepositoryView`] alongside the number of commits it is ahead of
+/// and behind its remote branch, if either is non-zero (e.g. "main ↑3 ↓12"). If the configured
+/// upstream is gone or the remote branch could not be compared against, it is displayed instead
+/// (e.g. "feature [origin/feature: gone]" or "main [origin/main: incomparable]").
+fn format_branch(report: &RepositoryView) -> String {
+    let mut formatted = report.branch.clone();
+    if let Some(upstream) = &report.upstream {
+        if upstream.ahead > 0 {
+            formatted.push_str(&format!(" ↑{}", upstream.ahead));
+        }
+        if upstream.behind > 0 {
+            formatted.push_str(&format!(" ↓{}", upstream.behind));
+        }
+    }
+    if let Some(gone_upstream) = &report.gone_upstream {
+        formatted.push_str(&format!(" [{gone_upstream}: gone]"));
+    }
+    if let Some(incomparable_upstream) = &report.incomparable_upstream {
+        formatted.push_str(&format!(" [{incomparable_upstream}: incomparable]"));
+    }
+    formatted
+}
+
+/// Join the parent directory and the name of a [`RepositoryView`] to form its full path. Only the
+/// name is used if the parent directory is not found.
+fn full_path(report: &RepositoryView) -> PathBuf {
+    match &report.parent {
+        Some(parent) => Path::new(parent).join(&report.name),
+        None => PathBuf::from(&report.name),
+    }
+}
+
+/// Format the number of entries for each kind of change in a [`RepositoryView`], if any, with a
+/// leading space (e.g. " +2 ~5 ?3 !1"). Kinds of changes without any entries are not included.
+fn format_changes(report: &RepositoryView) -> String {
+    let mut formatted = String::new();
+    if let Some(changes) = &report.changes {
+        for (symbol, count) in [
+            ('+', changes.staged),
+            ('~', changes.modified),
+            ('-', changes.deleted),
+            ('»', changes.renamed),
+            ('?', changes.untracked),
+            ('!', changes.conflicted),
+        ] {
+            if count > 0 {
+                formatted.push_str(&format!(" {symbol}{count}"));
+            }
+        }
+    }
+    formatted
+}
+
+/// Format a local branch other than the current branch alongside the number of commits it is
+/// ahead of and behind its remote branch and the number of commits not found on any remote branch
+/// (e.g. "feature ↑3 (3 unpushed)", "feature (no upstream, 2 unpushed)", "feature
+/// (origin/feature: gone)" or "feature (origin/feature: incomparable, 1 unpushed)").
+fn format_other_branch(branch: &BranchView) -> String {
+    let mut formatted = branch.name.clone();
+    let mut details = Vec::new();
+    match (
+        &branch.upstream,
+        &branch.gone_upstream,
+        &branch.incomparable_upstream,
+    ) {
+        (Some(upstream), _, _) => {
+            if upstream.ahead > 0 {
+                formatted.push_str(&format!(" ↑{}", upstream.ahead));
+            }
+            if upstream.behind > 0 {
+                formatted.push_str(&format!(" ↓{}", upstream.behind));
+            }
+        }
+        (None, Some(gone_upstream), _) => details.push(format!("{gone_upstream}: gone")),
+        (None, None, Some(incomparable_upstream)) => {
+            details.push(format!("{incomparable_upstream}: incomparable"))
+        }
+        (None, None, None) => details.push("no upstream".to_string()),
+    }
+    if branch.unpushed > 0 {
+        details.push(format!("{} unpushed", branch.unpushed));
+    }
+    if !details.is_empty() {
+        formatted.push_str(&format!(" ({})", details.join(", ")));
+    }
+    formatted
+}
+
+/// Format the number of stash entries in a [`RepositoryView`], if any, with a leading space (e.g.
+/// " $3").
+fn format_stashes(report: &RepositoryView) -> String {
+    match report.stashes.len() {
+        0 => String::new(),
+        count => format!(" ${count}"),
+    }
+}
+
+/// Format the operation in progress for a [`RepositoryView`], if any, with a leading space (e.g.
+/// " [rebase]").
+fn format_operation(report: &RepositoryView) -> String {
+    match &report.operation {
+        Some(operation) => format!(" [{}]", operation.as_str()),
+        None => String::new(),
+    }
+}
+
+/// Flatten a [`RepositoryCollection`] into a list of [`RepositoryViews`](RepositoryView) sorted
+/// alphabetically and then sorted by status.
+fn sort_by_status(reports: &RepositoryCollection) -> Vec<RepositoryView> {
+    let mut all_reports = Vec::new();
+    for grouped_report in reports {
+        all_reports.append(&mut grouped_report.1.clone());
+    }
+    all_reports.sort_by(|a, b| a.name.cmp(&b.name));
+    all_reports.sort_by(|a, b| a.status.as_str().cmp(b.status.as_str()));
+    all_reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::{Status, Upstream};
+
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn porcelain_quotes_every_field_except_the_path() -> anyhow::Result<()> {
+        let mut report = RepositoryView::finalize(
+            Path::new("/tmp/play/root/sp ace"),
+            Some("main".to_string()),
+            Status::Unpushed,
+            Some("/tmp/play/my remote".to_string()),
+            None,
+            Vec::new(),
+        )?;
+        report.upstream = Some(Upstream {
+            name: "origin/main".to_string(),
+            ahead: 1,
+            behind: 0,
+        });
+        report.nested_in = Some("/tmp/play/root/ne sted".to_string());
+        assert_eq!(
+            "unpushed main origin/main 1 0 - - 0 \"/tmp/play/my remote\" \"/tmp/play/root/ne sted\" - /tmp/play/root/sp ace",
+            format_porcelain(&report)
+        );
+
+        report.upstream = None;
+        report.incomparable_upstream = Some("origin/main".to_string());
+        assert!(format_porcelain(&report).starts_with("unpushed main origin/main - - - - 0 "));
+
+        let mut report = RepositoryView::finalize(
+            Path::new("/tmp/play/root/broken"),
+            None,
+            Status::Error,
+            None,
+            None,
+            Vec::new(),
+        )?;
+        report.error = Some("could not find repository".to_string());
+        assert_eq!(
+            "error unknown - - - - - 0 - - \"could not find repository\" /tmp/play/root/broken",
+            format_porcelain(&report)
+        );
+        Ok(())
+    }
+
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn summary_orders_statuses_by_count_then_name() -> anyhow::Result<()> {
+        let summarize = |statuses: &[Status]| -> anyhow::Result<String> {
+            let views = statuses
+                .iter()
+                .enumerate()
+                .map(|(index, status)| {
+                    RepositoryView::finalize(
+                        &Path::new("/tmp/play").join(index.to_string()),
+                        None,
+                        *status,
+                        None,
+                        None,
+                        Vec::new(),
+                    )
+                })
+                .collect::<Result<Vec<RepositoryView>>>()?;
+            let reports = RepositoryCollection::from([(None, views)]);
+            Ok(Summary::new(&reports).to_string())
+        };
+
+        assert_eq!("0 repos", summarize(&[])?);
+        assert_eq!("1 repo: 1 clean", summarize(&[Status::Clean])?);
+        assert_eq!(
+            "4 repos: 2 unclean, 1 clean, 1 unpushed",
+            summarize(&[
+                Status::Unpushed,
+                Status::Unclean,
+                Status::Clean,
+                Status::Unclean
+            ])?
+        );
+        assert_eq!(
+            "3 repos: 1 bare, 1 behind, 1 error",
+            summarize(&[Status::Error, Status::Behind, Status::Bare])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_other_branch_describes_the_upstream() {
+        let mut branch = BranchView {
+            name: "feature".to_string(),
+            upstream: None,
+            gone_upstream: None,
+            incomparable_upstream: None,
+            unpushed: 2,
+        };
+        assert_eq!(
+            "feature (no upstream, 2 unpushed)",
+            format_other_branch(&branch)
+        );
+        branch.incomparable_upstream = Some("origin/feature".to_string());
+        assert_eq!(
+            "feature (origin/feature: incomparable, 2 unpushed)",
+            format_other_branch(&branch)
+        );
+        branch.incomparable_upstream = None;
+        branch.gone_upstream = Some("origin/feature".to_string());
+        assert_eq!(
+            "feature (origin/feature: gone, 2 unpushed)",
+            format_other_branch(&branch)
+        );
+    }
+
+    #[test]
+    fn quote_porcelain_escapes_special_characters() {
+        assert_eq!("plain", quote_porcelain("plain"));
+        assert_eq!("-", quote_porcelain("-"));
+        assert_eq!("\"a b\"", quote_porcelain("a b"));
+        assert_eq!("\"a\\\"b\"", quote_porcelain("a\"b"));
+        assert_eq!("\"a\\\\b\"", quote_porcelain("a\\b"));
+        assert_eq!("\"a\\tb\\nc\"", quote_porcelain("a\tb\nc"));
+        assert_eq!("\"a\\001b\"", quote_porcelain("a\u{1}b"));
+    }
+
+    #[test]
+    fn escape_csv_quotes_special_characters() {
+        assert_eq!("plain", escape_csv("plain"));
+        assert_eq!("with\ttab", escape_csv("with\ttab"));
+        assert_eq!("\"a,b\"", escape_csv("a,b"));
+        assert_eq!("\"say \"\"hi\"\"\"", escape_csv("say \"hi\""));
+        assert_eq!("\"line\none\"", escape_csv("line\none"));
+    }
+
+    #[test]
+    fn escape_tsv_escapes_special_characters() {
+        assert_eq!("a,b \"c\"", escape_tsv("a,b \"c\""));
+        assert_eq!("a\\tb", escape_tsv("a\tb"));
+        assert_eq!("a\\nb", escape_tsv("a\nb"));
+        assert_eq!("a\\\\b", escape_tsv("a\\b"));
+    }
+}
@@ -29,12 +29,7 @@ impl ColorHarness {
     /// Writes the [`Status`] of the Git repository to `stdout`.
     pub fn write_status(&self, status: Status, status_width: usize) -> io::Result<()> {
         let mut stdout = StandardStream::stdout(self.color_choice);
-        stdout.set_color(ColorSpec::new().set_fg(Some(match status {
-            Status::Bare | Status::Unknown => Color::Red,
-            Status::Clean => Color::Green,
-            Status::Unpushed => Color::Blue,
-            Status::Unclean => Color::Yellow,
-        })))?;
+        stdout.set_color(ColorSpec::new().set_fg(Some(Self::status_color(status))))?;
         write!(
             &mut stdout,
             "{:<status_width$}",
@@ -44,24 +39,54 @@ impl ColorHarness {
         stdout.reset()
     }
 
-    /// Writes the input [`&str`] to `stdout` in bold.
-    pub fn write_bold(&self, input: &str, newline: bool) -> io::Result<()> {
-        self.write_color(input, newline, ColorSpec::new().set_bold(true))
+    /// Returns the [`Color`] used when writing the given [`Status`].
+    pub fn status_color(status: Status) -> Color {
+        match status {
+            Status::Bare | Status::Error | Status::Unknown => Color::Red,
+            Status::Behind => Color::Magenta,
+            Status::Clean => Color::Green,
+            Status::Diverged => Color::Cyan,
+            Status::Unpushed => Color::Blue,
+            Status::Unclean => Color::Yellow,
+        }
     }
 
-    /// Writes the input [`&str`] to `stdout` in gray (or cyan if in compatibility mode).
-    pub fn write_gray(&self, input: &str, newline: bool) -> io::Result<()> {
+    /// Returns the [`Color`] used for gray text (or cyan if in compatibility mode).
+    pub fn gray(&self) -> Color {
         // FIXME(nick): check why Color::Rg(128, 128, 128) breaks in tmux on macOS Terminal.app.
+        match &self.color_choice {
+            ColorChoice::Auto => Color::Cyan,
+            _ => Color::Ansi256(242),
+        }
+    }
+
+    /// Writes the input [`&str`] to `stdout` using the provided [`ColorSpec`] without a newline.
+    pub fn write_spec(&self, input: &str, color_spec: &ColorSpec) -> io::Result<()> {
+        let mut stdout = StandardStream::stdout(self.color_choice);
+        stdout.set_color(color_spec)?;
+        write!(&mut stdout, "{input}")?;
+        stdout.reset()
+    }
+
+    /// Writes the input [`&str`] to `stdout` in bold yellow, which indicates a warning.
+    pub fn write_warning(&self, input: &str, newline: bool) -> io::Result<()> {
         self.write_color(
             input,
             newline,
-            ColorSpec::new().set_fg(Some(match &self.color_choice {
-                ColorChoice::Auto => Color::Cyan,
-                _ => Color::Ansi256(242),
-            })),
+            ColorSpec::new().set_bold(true).set_fg(Some(Color::Yellow)),
         )
     }
 
+    /// Writes the input [`&str`] to `stdout` in bold.
+    pub fn write_bold(&self, input: &str, newline: bool) -> io::Result<()> {
+        self.write_color(input, newline, ColorSpec::new().set_bold(true))
+    }
+
+    /// Writes the input [`&str`] to `stdout` in gray (or cyan if in compatibility mode).
+    pub fn write_gray(&self, input: &str, newline: bool) -> io::Result<()> {
+        self.write_color(input, newline, ColorSpec::new().set_fg(Some(self.gray())))
+    }
+
     fn write_color(
         &self,
         input: &str,
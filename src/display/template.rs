@@ -0,0 +1,273 @@
+//! This module contains [`Template`], which renders [`RepositoryViews`](RepositoryView) using a
+//! user-defined format string.
+
+use std::io;
+
+use anyhow::{Result, bail};
+use termcolor::{Color, ColorSpec};
+
+use super::color::ColorHarness;
+use super::full_path;
+use crate::repository_view::RepositoryView;
+use crate::status::Status;
+
+/// A parsed format string, which is made up of literal text, placeholders for fields of a
+/// [`RepositoryView`] and color directives. Placeholders and directives are wrapped in braces
+/// (e.g. `{name}` or `{red}`) and literal braces are escaped by doubling them (i.e. `{{` and `}}`).
+#[derive(Debug)]
+pub(crate) struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Segment {
+    Directive(Directive),
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+#[remain::sorted]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Ahead,
+    Behind,
+    Branch,
+    Email,
+    Name,
+    Operation,
+    Path,
+    Stashes,
+    Status,
+    Url,
+}
+
+/// Color directives apply to all text following them until the `{reset}` directive is found.
+/// Directives are cumulative, so `{bold}{red}` results in bold, red text.
+#[remain::sorted]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    Blue,
+    Bold,
+    Cyan,
+    Gray,
+    Green,
+    Magenta,
+    Red,
+    Reset,
+    StatusColor,
+    Yellow,
+}
+
+impl Template {
+    /// Parse the format string into a [`Template`]. Unknown placeholders and directives, as well
+    /// as unmatched braces, result in an error.
+    pub(crate) fn parse(format: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = format.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut key = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => key.push(c),
+                            None => bail!("unclosed placeholder in format template: {format}"),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Self::parse_key(&key)?);
+                }
+                '}' => bail!(
+                    "unmatched \"}}\" in format template (use \"}}}}\" for a literal brace): {format}"
+                ),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+
+    fn parse_key(key: &str) -> Result<Segment> {
+        Ok(match key {
+            "ahead" => Segment::Placeholder(Placeholder::Ahead),
+            "behind" => Segment::Placeholder(Placeholder::Behind),
+            "branch" => Segment::Placeholder(Placeholder::Branch),
+            "email" => Segment::Placeholder(Placeholder::Email),
+            "name" => Segment::Placeholder(Placeholder::Name),
+            "operation" => Segment::Placeholder(Placeholder::Operation),
+            "path" => Segment::Placeholder(Placeholder::Path),
+            "stashes" => Segment::Placeholder(Placeholder::Stashes),
+            "status" => Segment::Placeholder(Placeholder::Status),
+            "url" => Segment::Placeholder(Placeholder::Url),
+            "blue" => Segment::Directive(Directive::Blue),
+            "bold" => Segment::Directive(Directive::Bold),
+            "cyan" => Segment::Directive(Directive::Cyan),
+            "gray" => Segment::Directive(Directive::Gray),
+            "green" => Segment::Directive(Directive::Green),
+            "magenta" => Segment::Directive(Directive::Magenta),
+            "red" => Segment::Directive(Directive::Red),
+            "reset" => Segment::Directive(Directive::Reset),
+            "status_color" => Segment::Directive(Directive::StatusColor),
+            "yellow" => Segment::Directive(Directive::Yellow),
+            key => bail!("unknown placeholder in format template: {{{key}}}"),
+        })
+    }
+
+    /// Write a single [`RepositoryView`] to `stdout` using the template, followed by a newline.
+    /// Color directives are routed through the provided [`ColorHarness`].
+    pub(crate) fn write(
+        &self,
+        report: &RepositoryView,
+        color_harness: &ColorHarness,
+    ) -> io::Result<()> {
+        let mut color_spec = ColorSpec::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => color_harness.write_spec(literal, &color_spec)?,
+                Segment::Placeholder(placeholder) => {
+                    color_harness.write_spec(&Self::render(*placeholder, report), &color_spec)?
+                }
+                Segment::Directive(directive) => {
+                    Self::apply(*directive, &mut color_spec, color_harness, report.status)
+                }
+            }
+        }
+        color_harness.write_spec("\n", &ColorSpec::new())
+    }
+
+    /// Apply a directive to the color specification used for all following text.
+    fn apply(
+        directive: Directive,
+        color_spec: &mut ColorSpec,
+        color_harness: &ColorHarness,
+        status: Status,
+    ) {
+        match directive {
+            Directive::Blue => {
+                color_spec.set_fg(Some(Color::Blue));
+            }
+            Directive::Bold => {
+                color_spec.set_bold(true);
+            }
+            Directive::Cyan => {
+                color_spec.set_fg(Some(Color::Cyan));
+            }
+            Directive::Gray => {
+                color_spec.set_fg(Some(color_harness.gray()));
+            }
+            Directive::Green => {
+                color_spec.set_fg(Some(Color::Green));
+            }
+            Directive::Magenta => {
+                color_spec.set_fg(Some(Color::Magenta));
+            }
+            Directive::Red => {
+                color_spec.set_fg(Some(Color::Red));
+            }
+            Directive::Reset => *color_spec = ColorSpec::new(),
+            Directive::StatusColor => {
+                color_spec.set_fg(Some(ColorHarness::status_color(status)));
+            }
+            Directive::Yellow => {
+                color_spec.set_fg(Some(Color::Yellow));
+            }
+        }
+    }
+
+    /// Render the value for a placeholder. Missing values are rendered as empty strings.
+    fn render(placeholder: Placeholder, report: &RepositoryView) -> String {
+        match placeholder {
+            Placeholder::Ahead => report
+                .upstream
+                .as_ref()
+                .map(|u| u.ahead.to_string())
+                .unwrap_or_default(),
+            Placeholder::Behind => report
+                .upstream
+                .as_ref()
+                .map(|u| u.behind.to_string())
+                .unwrap_or_default(),
+            Placeholder::Branch => report.branch.clone(),
+            Placeholder::Email => report.email.clone().unwrap_or_default(),
+            Placeholder::Name => report.name.clone(),
+            Placeholder::Operation => report
+                .operation
+                .map(|o| o.as_str().to_string())
+                .unwrap_or_default(),
+            Placeholder::Path => full_path(report).display().to_string(),
+            Placeholder::Stashes => report.stashes.len().to_string(),
+            Placeholder::Status => report.status.as_str().to_string(),
+            Placeholder::Url => report.url.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ColorMode;
+
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn parse_escaped_braces() -> Result<()> {
+        let template = Template::parse("{{{name}}}")?;
+        assert_eq!(
+            vec![
+                Segment::Literal("{".to_string()),
+                Segment::Placeholder(Placeholder::Name),
+                Segment::Literal("}".to_string()),
+            ],
+            template.segments
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors() {
+        let unknown = Template::parse("{name} {nope}").expect_err("unknown key should fail");
+        assert!(unknown.to_string().contains("{nope}"));
+        let unclosed = Template::parse("{name").expect_err("unclosed brace should fail");
+        assert!(unclosed.to_string().starts_with("unclosed placeholder"));
+        let stray = Template::parse("name}").expect_err("stray brace should fail");
+        assert!(stray.to_string().starts_with("unmatched \"}\""));
+    }
+
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn directives_accumulate_until_reset() -> Result<()> {
+        let template = Template::parse("{bold}{red}{reset}{green}")?;
+        let color_harness = ColorHarness::new(ColorMode::Never);
+        let mut color_spec = ColorSpec::new();
+        let mut observed = Vec::new();
+        for segment in &template.segments {
+            if let Segment::Directive(directive) = segment {
+                Template::apply(*directive, &mut color_spec, &color_harness, Status::Clean);
+                observed.push(color_spec.clone());
+            }
+        }
+
+        let mut bold = ColorSpec::new();
+        bold.set_bold(true);
+        let mut bold_red = bold.clone();
+        bold_red.set_fg(Some(Color::Red));
+        let mut green = ColorSpec::new();
+        green.set_fg(Some(Color::Green));
+        assert_eq!(vec![bold, bold_red, ColorSpec::new(), green], observed);
+        Ok(())
+    }
+}
@@ -26,14 +26,16 @@
     while_true
 )]
 
-use std::{env, fs, io::Write, path::PathBuf};
+use std::{env, fs, io::Write, path::PathBuf, process::ExitCode};
 
 use anyhow::Result;
 use clap::{CommandFactory, Parser};
 use clap_mangen::Man;
 use cli::Cli;
+use collector::RepositoryCollection;
 use config::{Config, DisplayMode};
 use log::debug;
+use status::Status;
 
 pub mod cli;
 pub mod collector;
@@ -44,8 +46,11 @@ pub mod status;
 
 /// Initializes the logger based on the debug flag and `RUST_LOG` environment variable, then
 /// parses CLI arguments and generates a [`Config`] by merging configurations as needed,
-/// and finally collects results and displays them.
-fn main() -> Result<()> {
+/// and finally collects results and displays them. If any repository could not be processed, a
+/// failing exit code is returned after all results have been displayed. When checking
+/// repositories, the exit code instead describes the failing statuses found (see
+/// [`Status::as_exit_code_bits`]).
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     env_logger::Builder::new()
@@ -73,7 +78,7 @@ fn main() -> Result<()> {
         file.write_all(&buffer)?;
 
         debug!("generated and wrote out man page");
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
 
     if let Some(found_display_mode_raw) = &cli.display_mode {
@@ -82,6 +87,53 @@ fn main() -> Result<()> {
     if let Some(found_color_mode) = &cli.color_mode {
         config.color_mode = *found_color_mode;
     }
+    if let Some(found_format) = &cli.format {
+        config.format = Some(found_format.clone());
+    }
+    if let Some(found_status) = &cli.status {
+        config.status = found_status.clone();
+    }
+    if let Some(found_exclude_status) = &cli.exclude_status {
+        config.exclude_status = found_exclude_status.clone();
+    }
+    if cli.summary_only {
+        config.summary_only = true;
+    }
+    if cli.all_branches {
+        config.all_branches = true;
+    }
+    if cli.check {
+        config.check = true;
+    }
+    if let Some(found_check_status) = &cli.check_status {
+        config.check_status = found_check_status.clone();
+    }
+    if let Some(found_ignore) = &cli.ignore {
+        config.ignore.extend(found_ignore.iter().cloned());
+    }
+    if cli.include_hidden {
+        config.include_hidden = true;
+    }
+    if cli.follow_symlinks {
+        config.follow_symlinks = true;
+    }
+    if cli.include_nested {
+        config.include_nested = true;
+    }
+    if let Some(found_allow_hidden) = &cli.allow_hidden {
+        config
+            .allow_hidden
+            .extend(found_allow_hidden.iter().cloned());
+    }
+    if let Some(found_max_depth) = cli.max_depth {
+        config.max_depth = Some(found_max_depth);
+    }
+    if cli.one_file_system {
+        config.one_file_system = true;
+    }
+    if cli.null_terminated {
+        config.null_terminated = true;
+    }
     if let Some(found_paths) = &cli.paths {
         let current_dir = env::current_dir()?;
         config.paths = found_paths
@@ -93,37 +145,78 @@ fn main() -> Result<()> {
 
     if cli.dry_run {
         config.print()?;
-    } else {
-        let (include_email, include_submodules) = match config.display_mode {
-            DisplayMode::Classic => (false, false),
-            DisplayMode::Json => (true, true),
-            DisplayMode::Standard | DisplayMode::StandardAlphabetical => (true, false),
-        };
-        for path in &config.paths {
-            debug!("processing path: {}", path.display());
-
-            let repository_collection = collector::run(path, include_email, include_submodules)?;
-            display::run(
-                config.display_mode,
-                config.color_mode,
-                &repository_collection,
-            )?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let (include_email, include_submodules) = match config.display_mode {
+        _ if config.format.is_some() => (true, false),
+        DisplayMode::Classic | DisplayMode::Porcelain => (false, false),
+        DisplayMode::Json | DisplayMode::JsonDocument | DisplayMode::Ndjson => (true, true),
+        DisplayMode::Csv
+        | DisplayMode::Standard
+        | DisplayMode::StandardAlphabetical
+        | DisplayMode::Tsv => (true, false),
+    };
+    let repository_collection = match config.display_mode {
+        DisplayMode::Ndjson if !config.summary_only && config.format.is_none() => {
+            collector::run_streaming(
+                &config.paths,
+                &config,
+                include_email,
+                include_submodules,
+                |view| match config.displays_status(view.status) {
+                    true => display::ndjson(view),
+                    false => Ok(()),
+                },
+            )?
         }
+        _ => collector::run(&config.paths, &config, include_email, include_submodules)?,
+    };
+    let displayed_collection = collector::filter(&repository_collection, |view| {
+        config.displays_status(view.status)
+    });
+    display::run(&config, &displayed_collection)?;
+
+    Ok(exit_code(&config, &repository_collection))
+}
+
+/// Determines the exit code for the [`RepositoryCollection`]. When checking repositories, the
+/// exit code bits of every failing status found are combined. Otherwise, or if no failing status
+/// was found, the exit code is only failing if any repository could not be processed.
+fn exit_code(config: &Config, repository_collection: &RepositoryCollection) -> ExitCode {
+    if config.check {
+        let exit_code_bits = repository_collection
+            .values()
+            .flatten()
+            .filter(|view| config.check_status.contains(&view.status))
+            .fold(0, |bits, view| bits | view.status.as_exit_code_bits());
+        if exit_code_bits != 0 {
+            debug!("at least one repository has a failing status: {exit_code_bits}");
+            return ExitCode::from(exit_code_bits);
+        }
+    }
+    if repository_collection
+        .values()
+        .flatten()
+        .any(|view| view.status == Status::Error)
+    {
+        debug!("at least one repository could not be processed");
+        return ExitCode::FAILURE;
     }
-    Ok(())
+    ExitCode::SUCCESS
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use collector::RepositoryCollection;
+    use git2::BranchType;
     use git2::ErrorCode;
     use git2::Oid;
     use git2::Signature;
     use git2::{Repository, RepositoryInitOptions};
     use repository_view::RepositoryView;
-    use status::Status;
+    use status::{Changes, Operation, Upstream};
     use std::fs::File;
     use std::path::{Path, PathBuf};
     use std::{fs, io};
@@ -142,6 +235,7 @@ mod tests {
         //     ├── two (repo)
         //     ├── three (repo)
         //     ├── eight (worktree repo)
+        //     ├── nine.git (bare repo)
         //     └── nested
         //         ├── four (repo)
         //         ├── five (repo)
@@ -159,6 +253,7 @@ mod tests {
         let repo_six = create_directory(&nested, "six")?;
         let repo_seven = create_directory(&nested, "seven")?;
         // repo_eight doesn't need a dir. It's created via 'worktree add'
+        let repo_nine = create_directory(&root, "nine.git")?;
 
         // Setup repo opts
         let mut opts = RepositoryInitOptions::new();
@@ -210,6 +305,11 @@ mod tests {
         let worktree_path = root.path().join("eight");
         repository.worktree("working-in-a-tree", &worktree_path, None)?;
 
+        // Repo Nine
+        let mut bare_opts = RepositoryInitOptions::new();
+        bare_opts.initial_head(initial_head).bare(true);
+        Repository::init_opts(&repo_nine, &bare_opts)?;
+
         // Generate the collection directly with a default config and ensure the resulting views
         // match what we expect.
         let mut expected_collection = RepositoryCollection::new();
@@ -227,6 +327,14 @@ mod tests {
                 None,
                 Vec::with_capacity(0),
             )?,
+            RepositoryView::finalize(
+                &repo_nine,
+                Some("HEAD".to_string()),
+                Status::Bare,
+                None,
+                None,
+                Vec::with_capacity(0),
+            )?,
             RepositoryView::finalize(
                 &repo_one,
                 Some("HEAD".to_string()),
@@ -253,6 +361,7 @@ mod tests {
             )?,
         ];
         expected_views.sort_by(|a, b| a.name.cmp(&b.name));
+        set_expected_changes(&mut expected_views);
         expected_collection.insert(Some(expected_views_key), expected_views);
 
         // Add nested views to the expected collection.
@@ -295,21 +404,653 @@ mod tests {
             )?,
         ];
         nested_expected_views_raw.sort_by(|a, b| a.name.cmp(&b.name));
+        set_expected_changes(&mut nested_expected_views_raw);
         expected_collection.insert(Some(nested_expected_views_key), nested_expected_views_raw);
 
-        // Generate a collection.
-        let found_collection = collector::run(root.path(), false, false)?;
+        // Generate a collection for the root directory alone and for overlapping paths, which
+        // should not result in duplicate views.
+        for paths in [
+            vec![root.path().to_path_buf()],
+            vec![root.path().to_path_buf(), nested.clone()],
+        ] {
+            let found_collection =
+                collector::run(&paths, &Config::try_config_default()?, false, false)?;
 
-        // Ensure the found collection matches our expected one. Sort the collection for the
-        // assertion.
-        let mut found_collection_sorted = RepositoryCollection::new();
-        for (key, mut value) in found_collection {
-            value.sort_by(|a, b| a.name.cmp(&b.name));
-            found_collection_sorted.insert(key, value);
+            // Ensure the found collection matches our expected one. Sort the collection for the
+            // assertion.
+            let mut found_collection_sorted = RepositoryCollection::new();
+            for (key, mut value) in found_collection {
+                value.sort_by(|a, b| a.name.cmp(&b.name));
+                found_collection_sorted.insert(key, value);
+            }
+            assert_eq!(
+                expected_collection,     // expected
+                found_collection_sorted  // actual
+            );
         }
+        Ok(())
+    }
+
+    /// This test ensures that the number of commits ahead of and behind the remote branch are
+    /// collected for repositories whose local branches are behind or have diverged from their
+    /// remote branches, that configured upstreams that are gone are distinguished from upstreams
+    /// that were never configured, and that branches that cannot be compared against their remote
+    /// branches are not assumed to be unpushed.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn upstream() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let behind = create_directory(&root, "behind")?;
+        let diverged = create_directory(&root, "diverged")?;
+        let fork = create_directory(&root, "fork")?;
+        let gone = create_directory(&root, "gone")?;
+
+        let mut opts = RepositoryInitOptions::new();
+        opts.initial_head("main");
+
+        // Both the local and remote branches share the first commit, but then the remote branch
+        // receives a commit of its own.
+        let repository = Repository::init_opts(&behind, &opts)?;
+        repository.remote("origin", "https://github.com/nickgerace/gfold")?;
+        commit(&repository, "HEAD")?;
+        commit(&repository, "refs/remotes/origin/main")?;
+
+        // Both the local and remote branches share the first commit, but then each receive a
+        // commit of their own.
+        let repository = Repository::init_opts(&diverged, &opts)?;
+        repository.remote("origin", "https://github.com/nickgerace/gfold")?;
+        let shared = commit(&repository, "HEAD")?;
+        commit(&repository, "refs/remotes/origin/main")?;
+        create_file(&diverged)?;
+        let mut index = repository.index()?;
+        index.add_path(Path::new("file"))?;
+        index.write()?;
+        commit(&repository, "HEAD")?;
+
+        // The local branch tracks a differently named branch on a remote other than "origin".
+        let repository = Repository::init_opts(&fork, &opts)?;
+        repository.remote("origin", "https://github.com/nickgerace/gfold")?;
+        repository.remote("upstream", "https://github.com/nickgerace/gfold")?;
+        let oid = commit(&repository, "HEAD")?;
+        repository.reference("refs/remotes/upstream/trunk", oid, false, "")?;
+        repository
+            .find_branch("main", BranchType::Local)?
+            .set_upstream(Some("upstream/trunk"))?;
+
+        // The local branch tracks a remote branch that has since been deleted, but all of its
+        // commits can be found on another remote branch.
+        let repository = Repository::init_opts(&gone, &opts)?;
+        repository.remote("origin", "https://github.com/nickgerace/gfold")?;
+        let oid = commit(&repository, "HEAD")?;
+        repository.reference("refs/remotes/origin/trunk", oid, false, "")?;
+        let mut deleted = repository.reference("refs/remotes/origin/main", oid, false, "")?;
+        repository
+            .find_branch("main", BranchType::Local)?
+            .set_upstream(Some("origin/main"))?;
+        deleted.delete()?;
+
+        let view = RepositoryView::new(&behind, false, false, false)?;
+        assert_eq!(Status::Behind, view.status);
         assert_eq!(
-            expected_collection,     // expected
-            found_collection_sorted  // actual
+            Some(Upstream {
+                name: "origin/main".to_string(),
+                ahead: 0,
+                behind: 1
+            }),
+            view.upstream
+        );
+
+        let view = RepositoryView::new(&diverged, false, false, false)?;
+        assert_eq!(Status::Diverged, view.status);
+        assert_eq!(
+            Some(Upstream {
+                name: "origin/main".to_string(),
+                ahead: 1,
+                behind: 1
+            }),
+            view.upstream
+        );
+
+        let view = RepositoryView::new(&fork, false, false, false)?;
+        assert_eq!(Status::Clean, view.status);
+        assert_eq!(
+            Some(Upstream {
+                name: "upstream/trunk".to_string(),
+                ahead: 0,
+                behind: 0
+            }),
+            view.upstream
+        );
+        assert_eq!(None, view.gone_upstream);
+
+        let view = RepositoryView::new(&gone, false, false, false)?;
+        assert_eq!(Status::Clean, view.status);
+        assert_eq!(None, view.upstream);
+        assert_eq!(Some("origin/main".to_string()), view.gone_upstream);
+
+        // Once the local branch has a commit that cannot be found on any remote branch, it is
+        // unpushed.
+        commit(&Repository::open(&gone)?, "HEAD")?;
+        let view = RepositoryView::new(&gone, false, false, false)?;
+        assert_eq!(Status::Unpushed, view.status);
+        assert_eq!(Some("origin/main".to_string()), view.gone_upstream);
+
+        // Once the commit shared by both branches is missing (e.g. in a shallow clone), the
+        // branches cannot be compared and the local branch is not assumed to be unpushed.
+        let shared = shared.to_string();
+        fs::remove_file(
+            diverged
+                .join(".git")
+                .join("objects")
+                .join(&shared[..2])
+                .join(&shared[2..]),
+        )?;
+        let view = RepositoryView::new(&diverged, false, false, false)?;
+        assert_eq!(Status::Clean, view.status);
+        assert_eq!(None, view.upstream);
+        assert_eq!(Some("origin/main".to_string()), view.incomparable_upstream);
+
+        // The same is true for every local branch when all branches are included.
+        let view = RepositoryView::new(&diverged, false, false, true)?;
+        assert_eq!(1, view.branches.len());
+        assert_eq!("main", view.branches[0].name);
+        assert_eq!(None, view.branches[0].upstream);
+        assert_eq!(None, view.branches[0].gone_upstream);
+        assert_eq!(
+            Some("origin/main".to_string()),
+            view.branches[0].incomparable_upstream
+        );
+        Ok(())
+    }
+
+    /// This test ensures that a repository that cannot be opened is collected with an error
+    /// rather than preventing healthy repositories from being collected.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn errors() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let healthy = create_directory(&root, "healthy")?;
+        let broken = create_directory(&root, "broken")?;
+
+        Repository::init(&healthy)?;
+
+        // Mimic a worktree whose "gitdir" has been deleted.
+        fs::write(
+            broken.join(".git"),
+            format!("gitdir: {}\n", root.path().join("deleted").display()),
+        )?;
+
+        let views = collect_views(&root, &Config::try_config_default()?)?;
+        assert_eq!(2, views.len());
+        assert_eq!("broken", views[0].name);
+        assert_eq!(Status::Error, views[0].status);
+        assert!(views[0].error.is_some());
+        assert_eq!("healthy", views[1].name);
+        assert_eq!(Status::Clean, views[1].status);
+        assert!(views[1].error.is_none());
+        Ok(())
+    }
+
+    /// This test ensures that repositories are filtered by status and that excluded statuses take
+    /// precedence over included statuses.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn status_filters() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let clean = create_directory(&root, "clean")?;
+        let unclean = create_directory(&root, "unclean")?;
+        let broken = create_directory(&root, "broken")?;
+
+        Repository::init(&clean)?;
+        Repository::init(&unclean)?;
+        create_file(&unclean)?;
+        fs::write(
+            broken.join(".git"),
+            format!("gitdir: {}\n", root.path().join("deleted").display()),
+        )?;
+
+        let mut config = Config::try_config_default()?;
+        config.status = vec![Status::Clean, Status::Error];
+        config.exclude_status = vec![Status::Error];
+        assert!(config.displays_status(Status::Clean));
+        assert!(!config.displays_status(Status::Error));
+        assert!(!config.displays_status(Status::Unclean));
+
+        let collection = collector::run(&[root.path().to_path_buf()], &config, false, false)?;
+        let names = collector::filter(&collection, |view| config.displays_status(view.status))
+            .into_values()
+            .flatten()
+            .map(|view| view.name)
+            .collect::<Vec<String>>();
+        assert_eq!(vec!["clean".to_string()], names);
+
+        // Without any included statuses, every status except the excluded ones is displayed.
+        config.status.clear();
+        assert!(config.displays_status(Status::Unclean));
+        assert!(!config.displays_status(Status::Error));
+        Ok(())
+    }
+
+    /// This test ensures that the exit code combines the bits of every failing status found when
+    /// checking repositories and otherwise only fails if a repository could not be processed.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn exit_codes() -> anyhow::Result<()> {
+        let collection_with = |statuses: &[Status]| -> anyhow::Result<RepositoryCollection> {
+            let views = statuses
+                .iter()
+                .map(|status| {
+                    RepositoryView::finalize(
+                        &Path::new("/tmp").join(status.as_str()),
+                        None,
+                        *status,
+                        None,
+                        None,
+                        Vec::new(),
+                    )
+                })
+                .collect::<anyhow::Result<Vec<RepositoryView>>>()?;
+            Ok(RepositoryCollection::from([(
+                Some("/tmp".to_string()),
+                views,
+            )]))
+        };
+        let failing = collection_with(&[
+            Status::Clean,
+            Status::Unclean,
+            Status::Unpushed,
+            Status::Error,
+        ])?;
+        let clean = collection_with(&[Status::Clean, Status::Bare])?;
+
+        let mut config = Config::try_config_default()?;
+        assert_eq!(ExitCode::FAILURE, exit_code(&config, &failing));
+        assert_eq!(ExitCode::SUCCESS, exit_code(&config, &clean));
+
+        // The default failing statuses are unclean, unpushed, behind, diverged and error.
+        config.check = true;
+        assert_eq!(ExitCode::from(4 | 8 | 32), exit_code(&config, &failing));
+        assert_eq!(ExitCode::SUCCESS, exit_code(&config, &clean));
+        assert_eq!(
+            ExitCode::from(24),
+            exit_code(
+                &config,
+                &collection_with(&[Status::Behind, Status::Diverged])?
+            )
+        );
+
+        // Errors that are not failing statuses still result in a failing exit code.
+        config.check_status = vec![Status::Unclean];
+        assert_eq!(ExitCode::from(4), exit_code(&config, &failing));
+        assert_eq!(
+            ExitCode::FAILURE,
+            exit_code(&config, &collection_with(&[Status::Clean, Status::Error])?)
+        );
+
+        config.check_status = vec![Status::Bare];
+        assert_eq!(ExitCode::from(64), exit_code(&config, &clean));
+        Ok(())
+    }
+
+    /// This test ensures that directories matching ignore patterns from the config and from
+    /// ".gfoldignore" files are not traversed.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn ignore() -> anyhow::Result<()> {
+        // Temporary directory structure:
+        // └── root
+        //     ├── .gfoldignore (vendor)
+        //     ├── keep (repo)
+        //     ├── node_modules
+        //     │   └── package (repo)
+        //     ├── vendor (repo)
+        //     └── nested
+        //         ├── .gfoldignore (skip)
+        //         └── skip (repo)
+        let root = tempdir()?;
+        let keep = create_directory(&root, "keep")?;
+        let node_modules = create_directory(&root, "node_modules")?;
+        let package = create_directory(&node_modules, "package")?;
+        let vendor = create_directory(&root, "vendor")?;
+        let nested = create_directory(&root, "nested")?;
+        let skip = create_directory(&nested, "skip")?;
+        for path in [&keep, &package, &vendor, &skip] {
+            Repository::init(path)?;
+        }
+        fs::write(root.path().join(".gfoldignore"), "vendor\n")?;
+        fs::write(nested.join(".gfoldignore"), "skip\n")?;
+
+        let mut config = Config::try_config_default()?;
+        config.ignore = vec!["node_modules/".to_string()];
+        assert_eq!(vec!["keep"], collect_names(&root, &config)?);
+        Ok(())
+    }
+
+    /// This test ensures that repositories nested inside the working trees of other repositories
+    /// are found (excluding registered submodules) when nested repositories are included.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn nested() -> anyhow::Result<()> {
+        // Temporary directory structure:
+        // └── root
+        //     └── outer (repo)
+        //         ├── .gitmodules (submodule)
+        //         ├── submodule (repo)
+        //         └── vendor
+        //             └── inner (repo)
+        let root = tempdir()?;
+        let outer = create_directory(&root, "outer")?;
+        let submodule = create_directory(&outer, "submodule")?;
+        let vendor = create_directory(&outer, "vendor")?;
+        let inner = create_directory(&vendor, "inner")?;
+        for path in [&outer, &submodule, &inner] {
+            Repository::init(path)?;
+        }
+        fs::write(
+            outer.join(".gitmodules"),
+            "[submodule \"submodule\"]\n\tpath = submodule\n\turl = https://github.com/nickgerace/gfold\n",
+        )?;
+
+        let mut config = Config::try_config_default()?;
+        assert_eq!(vec!["outer"], collect_names(&root, &config)?);
+
+        config.include_nested = true;
+        let views = collect_views(&root, &config)?;
+        assert_eq!(2, views.len());
+        assert_eq!("inner", views[0].name);
+        assert_eq!(outer.to_str().map(|s| s.to_string()), views[0].nested_in);
+        assert_eq!("outer", views[1].name);
+        assert_eq!(None, views[1].nested_in);
+        Ok(())
+    }
+
+    /// This test ensures that traversal stops at the maximum depth, where repositories found at
+    /// the maximum depth are included, but their siblings' children are not traversed.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn max_depth() -> anyhow::Result<()> {
+        // Temporary directory structure:
+        // └── root
+        //     └── org
+        //         ├── repo (repo)
+        //         └── deep
+        //             └── x
+        //                 └── r (repo)
+        let root = tempdir()?;
+        let org = create_directory(&root, "org")?;
+        let repo = create_directory(&org, "repo")?;
+        let deep = create_directory(&org, "deep")?;
+        let x = create_directory(&deep, "x")?;
+        let r = create_directory(&x, "r")?;
+        for path in [&repo, &r] {
+            Repository::init(path)?;
+        }
+
+        let mut config = Config::try_config_default()?;
+        config.max_depth = Some(2);
+        assert_eq!(vec!["repo"], collect_names(&root, &config)?);
+
+        config.max_depth = Some(0);
+        assert!(collect_names(&root, &config)?.is_empty());
+        Ok(())
+    }
+
+    /// This test ensures that hidden directories are skipped unless they are allowed or all hidden
+    /// directories are included, and that ".git" directories are never traversed.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn hidden() -> anyhow::Result<()> {
+        // Temporary directory structure:
+        // └── root
+        //     ├── .allowed
+        //     │   └── one (repo)
+        //     ├── .hidden
+        //     │   └── two (repo)
+        //     └── three (repo)
+        //         └── .git
+        //             └── four (repo)
+        let root = tempdir()?;
+        let allowed = create_directory(&root, ".allowed")?;
+        let one = create_directory(&allowed, "one")?;
+        let hidden = create_directory(&root, ".hidden")?;
+        let two = create_directory(&hidden, "two")?;
+        let three = create_directory(&root, "three")?;
+        for path in [&one, &two, &three] {
+            Repository::init(path)?;
+        }
+        Repository::init(three.join(".git").join("four"))?;
+
+        let mut config = Config::try_config_default()?;
+        assert_eq!(vec!["three"], collect_names(&root, &config)?);
+
+        config.allow_hidden = vec![".allowed".to_string()];
+        assert_eq!(vec!["one", "three"], collect_names(&root, &config)?);
+
+        config.allow_hidden = Vec::new();
+        config.include_hidden = true;
+        config.include_nested = true;
+        assert_eq!(vec!["one", "three", "two"], collect_names(&root, &config)?);
+        Ok(())
+    }
+
+    /// This test ensures that following symbolic links terminates for links that form cycles and
+    /// that repositories reachable via multiple links are only found once under their canonical
+    /// paths.
+    #[cfg(unix)]
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn symlinks() -> anyhow::Result<()> {
+        // Temporary directory structure:
+        // └── root
+        //     ├── link -> repos/a
+        //     ├── loop
+        //     │   └── up -> ..
+        //     └── repos
+        //         └── a (repo)
+        let root = tempdir()?;
+        let repos = create_directory(&root, "repos")?;
+        let a = create_directory(&repos, "a")?;
+        let cycle = create_directory(&root, "loop")?;
+        Repository::init(&a)?;
+        std::os::unix::fs::symlink("..", cycle.join("up"))?;
+        std::os::unix::fs::symlink(&a, root.path().join("link"))?;
+
+        let mut config = Config::try_config_default()?;
+        config.follow_symlinks = true;
+        let views = collect_views(&root, &config)?;
+        assert_eq!(1, views.len());
+        assert_eq!("a", views[0].name);
+        assert_eq!(
+            repos.canonicalize()?.to_str().map(|s| s.to_string()),
+            views[0].parent
+        );
+
+        // A directory reached via a symbolic link at the maximum depth must not prevent the same
+        // directory from being traversed when it is reached at a shallower depth.
+        // └── shallow
+        //     ├── a1..a30
+        //     │   └── link -> ../b
+        //     └── b
+        //         └── r1..r40 (repos)
+        let shallow = create_directory(&root, "shallow")?;
+        let b = create_directory(&shallow, "b")?;
+        for index in 1..=40 {
+            Repository::init(create_directory(&b, &format!("r{index}"))?)?;
+        }
+        for index in 1..=30 {
+            let a = create_directory(&shallow, &format!("a{index}"))?;
+            std::os::unix::fs::symlink("../b", a.join("link"))?;
+        }
+        config.max_depth = Some(2);
+        assert_eq!(40, collect_names(&shallow, &config)?.len());
+        Ok(())
+    }
+
+    /// This test ensures that the entries in the index and working tree are counted for each kind
+    /// of change in an unclean repository.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn changes() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let repo = create_directory(&root, "repo")?;
+        let repository = Repository::init(&repo)?;
+
+        // Commit three files, then modify one, delete another, stage a rename of the last, stage a
+        // new file and create an untracked file.
+        let mut index = repository.index()?;
+        for name in ["modified", "deleted", "renamed"] {
+            fs::write(repo.join(name), name)?;
+            index.add_path(Path::new(name))?;
+        }
+        index.write()?;
+        commit(&repository, "HEAD")?;
+        fs::write(repo.join("modified"), "after")?;
+        fs::remove_file(repo.join("deleted"))?;
+        fs::rename(repo.join("renamed"), repo.join("moved"))?;
+        index.remove_path(Path::new("renamed"))?;
+        index.add_path(Path::new("moved"))?;
+        fs::write(repo.join("staged"), "new")?;
+        index.add_path(Path::new("staged"))?;
+        index.write()?;
+        fs::write(repo.join("untracked"), "new")?;
+
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(Status::Unclean, view.status);
+        assert_eq!(
+            Some(Changes {
+                staged: 2,
+                modified: 1,
+                deleted: 1,
+                renamed: 1,
+                untracked: 1,
+                conflicted: 0,
+            }),
+            view.changes
+        );
+        Ok(())
+    }
+
+    /// This test ensures that commits on local branches other than the current branch are only
+    /// considered when all branches are included.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn branches() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let repo = create_directory(&root, "repo")?;
+        let mut opts = RepositoryInitOptions::new();
+        opts.initial_head("main");
+
+        // The current branch matches its remote branch, but another branch has a commit that
+        // cannot be found on any remote branch.
+        let repository = Repository::init_opts(&repo, &opts)?;
+        repository.remote("origin", "https://github.com/nickgerace/gfold")?;
+        let oid = commit(&repository, "HEAD")?;
+        repository.reference("refs/remotes/origin/main", oid, false, "")?;
+        repository.branch("feature", &repository.find_commit(oid)?, false)?;
+        commit(&repository, "refs/heads/feature")?;
+
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(Status::Clean, view.status);
+        assert!(view.branches.is_empty());
+
+        let view = RepositoryView::new(&repo, false, false, true)?;
+        assert_eq!(Status::Unpushed, view.status);
+        assert_eq!(2, view.branches.len());
+        assert_eq!("feature", view.branches[0].name);
+        assert_eq!(None, view.branches[0].upstream);
+        assert_eq!(1, view.branches[0].unpushed);
+        assert_eq!("main", view.branches[1].name);
+        assert_eq!(0, view.branches[1].unpushed);
+
+        // Once the current branch is behind its remote branch, the commit on the other branch
+        // means that the repository has diverged.
+        let signature = Signature::now("Bob", "bob@bob")?;
+        let head = repository.find_commit(oid)?;
+        repository.commit(
+            Some("refs/remotes/origin/main"),
+            &signature,
+            &signature,
+            "remote",
+            &head.tree()?,
+            &[&head],
+        )?;
+
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(Status::Behind, view.status);
+
+        let view = RepositoryView::new(&repo, false, false, true)?;
+        assert_eq!(Status::Diverged, view.status);
+        assert_eq!(1, view.branches[0].unpushed);
+        Ok(())
+    }
+
+    /// This test ensures that stash entries are collected, even if the working tree is clean.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn stashes() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let repo = create_directory(&root, "repo")?;
+        let mut repository = Repository::init(&repo)?;
+
+        let mut index = repository.index()?;
+        fs::write(repo.join("file"), "before")?;
+        index.add_path(Path::new("file"))?;
+        index.write()?;
+        commit(&repository, "HEAD")?;
+
+        let signature = Signature::now("Bob", "bob@bob")?;
+        for message in ["first", "second"] {
+            fs::write(repo.join("file"), message)?;
+            repository.stash_save(&signature, message, None)?;
+        }
+
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(Status::Clean, view.status);
+        assert_eq!(2, view.stashes.len());
+        assert_eq!(0, view.stashes[0].index);
+        assert!(view.stashes[0].message.ends_with("second"));
+        assert_eq!(1, view.stashes[1].index);
+        assert!(view.stashes[1].message.ends_with("first"));
+        Ok(())
+    }
+
+    /// This test ensures that an operation in progress is found, using a merge stopped due to
+    /// conflicts.
+    #[allow(clippy::panic_in_result_fn)]
+    #[test]
+    fn operation() -> anyhow::Result<()> {
+        let root = tempdir()?;
+        let repo = create_directory(&root, "repo")?;
+        let mut opts = RepositoryInitOptions::new();
+        opts.initial_head("main");
+        let repository = Repository::init_opts(&repo, &opts)?;
+
+        // Change the same file differently on two branches, then merge one into the other.
+        let mut index = repository.index()?;
+        fs::write(repo.join("file"), "base")?;
+        index.add_path(Path::new("file"))?;
+        index.write()?;
+        let base = commit(&repository, "HEAD")?;
+        repository.branch("other", &repository.find_commit(base)?, false)?;
+        for (contents, update_ref) in [("other", "refs/heads/other"), ("main", "HEAD")] {
+            fs::write(repo.join("file"), contents)?;
+            index.add_path(Path::new("file"))?;
+            index.write()?;
+            commit(&repository, update_ref)?;
+        }
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(None, view.operation);
+
+        let other = repository.find_branch("other", BranchType::Local)?;
+        let annotated = repository.reference_to_annotated_commit(other.get())?;
+        repository.merge(&[&annotated], None, None)?;
+
+        let view = RepositoryView::new(&repo, false, false, false)?;
+        assert_eq!(Some(Operation::Merge), view.operation);
+        assert_eq!(Status::Unclean, view.status);
+        assert_eq!(
+            Some(1),
+            view.changes.as_ref().map(|changes| changes.conflicted)
         );
         Ok(())
     }
@@ -326,12 +1067,44 @@ mod tests {
         Ok(new_directory)
     }
 
+    /// Collects the views for all repositories found within the root directory, sorted by name.
+    fn collect_views<P: AsRef<Path>>(
+        root: P,
+        config: &Config,
+    ) -> anyhow::Result<Vec<RepositoryView>> {
+        let mut views = collector::run(&[root.as_ref().to_path_buf()], config, false, false)?
+            .into_values()
+            .flatten()
+            .collect::<Vec<RepositoryView>>();
+        views.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(views)
+    }
+
+    /// Collects the names of all repositories found within the root directory, sorted by name.
+    fn collect_names<P: AsRef<Path>>(root: P, config: &Config) -> anyhow::Result<Vec<String>> {
+        Ok(collect_views(root, config)?
+            .into_iter()
+            .map(|view| view.name)
+            .collect())
+    }
+
     fn create_file<P: AsRef<Path>>(parent: P) -> io::Result<()> {
         let parent = parent.as_ref();
         File::create(parent.join("file"))?;
         Ok(())
     }
 
+    /// Sets the changes for unclean views, which each contain a single untracked file created via
+    /// [`create_file`].
+    fn set_expected_changes(views: &mut [RepositoryView]) {
+        for view in views.iter_mut().filter(|v| v.status == Status::Unclean) {
+            view.changes = Some(Changes {
+                untracked: 1,
+                ..Changes::default()
+            });
+        }
+    }
+
     fn commit_head_and_create_branch(repository: &Repository, name: &str) -> anyhow::Result<()> {
         // We need to commit at least once before branching.
         let commit_oid = commit(repository, "HEAD")?;
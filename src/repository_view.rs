@@ -4,13 +4,17 @@
 use std::path::Path;
 
 use anyhow::{Result, anyhow, bail};
+pub use branch_view::BranchView;
 use git2::Repository;
 use log::{debug, trace};
 use serde::{Deserialize, Serialize};
+use stash_view::StashView;
 use submodule_view::SubmoduleView;
 
-use crate::status::Status;
+use crate::status::{Changes, Findings, Operation, Status, Upstream};
 
+mod branch_view;
+mod stash_view;
 mod submodule_view;
 
 /// A collection of results for a Git repository at a given path.
@@ -25,36 +29,93 @@ pub struct RepositoryView {
 
     /// The parent directory of the `path` field. The value will be `None` if a parent is not found.
     pub parent: Option<String>,
+    /// The path of the closest repository whose working tree contains this repository. The value
+    /// will be `None` if the repository is not nested inside another repository.
+    pub nested_in: Option<String>,
     /// The remote origin URL. The value will be `None` if the URL cannot be found.
     pub url: Option<String>,
+    /// The comparison of the current branch against its remote branch. The value will be `None`
+    /// if the remote branch cannot be found.
+    pub upstream: Option<Upstream>,
+    /// The shorthand name of the upstream configured for the current branch if it could not be
+    /// found (e.g. the remote branch was deleted after merging). The value will be `None` if the
+    /// upstream was found or was never configured.
+    pub gone_upstream: Option<String>,
+    /// The shorthand name of the remote branch for the current branch if it was found, but could
+    /// not be compared against (e.g. history is missing in a shallow clone). The value will be
+    /// `None` otherwise.
+    pub incomparable_upstream: Option<String>,
+    /// The number of entries in the index and working tree for each kind of change. The value
+    /// will be `None` unless the `status` field is [`Status::Unclean`].
+    pub changes: Option<Changes>,
+    /// The operation that is in progress (e.g. a rebase stopped due to conflicts). The value will
+    /// be `None` if no operation is in progress.
+    pub operation: Option<Operation>,
 
     /// The email used in either the local or global config for the repository.
     pub email: Option<String>,
     /// Views of submodules found within the repository.
     pub submodules: Vec<SubmoduleView>,
+    /// Views of stash entries found within the repository.
+    pub stashes: Vec<StashView>,
+    /// Views of all local branches found within the repository. The value will be empty unless
+    /// all branches are included.
+    pub branches: Vec<BranchView>,
+    /// The error encountered while processing the repository. The value will be `None` unless the
+    /// `status` field is [`Status::Error`].
+    pub error: Option<String>,
 }
 
 impl RepositoryView {
-    /// Generates a collector for a given path.
+    /// Generates a collector for a given path. If all branches are included and any local branch
+    /// contains commits not found on any remote branch, a clean repository will be considered
+    /// unpushed and a repository whose current branch is behind will be considered diverged.
     pub fn new(
         repo_path: &Path,
         include_email: bool,
         include_submodules: bool,
+        include_branches: bool,
     ) -> Result<RepositoryView> {
         debug!(
             "attempting to generate collector for repository_view at path: {}",
             repo_path.display()
         );
 
-        let repo = Repository::open(repo_path)?;
-        let (status, head, remote) = Status::find(&repo)?;
+        // Stash entries are gathered first since doing so requires a mutable repository.
+        let mut repo = Repository::open(repo_path)?;
+        let stashes = StashView::list(&mut repo)?;
+        let Findings {
+            mut status,
+            head,
+            remote,
+            upstream,
+            gone_upstream,
+            incomparable_upstream,
+            changes,
+        } = Status::find(&repo)?;
 
-        let submodules = if include_submodules {
+        let submodules = if include_submodules && !repo.is_bare() {
             SubmoduleView::list(&repo)?
         } else {
             Vec::with_capacity(0)
         };
 
+        // Every commit is unpushed if there are no remotes, so we only consider unpushed commits
+        // on other branches if a remote exists.
+        let remote_name = remote.as_ref().and_then(|r| r.name());
+        let branches = match include_branches && !repo.is_bare() {
+            true => BranchView::list(&repo, remote_name)?,
+            false => Vec::with_capacity(0),
+        };
+        if remote_name.is_some() && branches.iter().any(|b| b.unpushed > 0) {
+            debug!("at least one branch contains unpushed commits");
+            status = match status {
+                Status::Clean => Status::Unpushed,
+                Status::Behind => Status::Diverged,
+                status => status,
+            };
+        }
+
         let branch = match &head {
             Some(head) => head
                 .shorthand()
@@ -76,17 +137,42 @@ impl RepositoryView {
             "finalized collector collection for repository_view at path: {}",
             repo_path.display()
         );
-        RepositoryView::finalize(
+        let mut view = RepositoryView::finalize(
             repo_path,
             Some(branch.to_string()),
             status,
             url,
             email,
             submodules,
-        )
+        )?;
+        view.upstream = upstream;
+        view.gone_upstream = gone_upstream;
+        view.incomparable_upstream = incomparable_upstream;
+        view.changes = changes;
+        view.operation = Operation::find(&repo);
+        view.stashes = stashes;
+        view.branches = branches;
+        Ok(view)
+    }
+
+    /// Generates a collector for a repository at a given path that could not be processed due to
+    /// an error. The error is preserved so that it can be displayed alongside other results.
+    pub fn from_error(repo_path: &Path, error: &anyhow::Error) -> Result<RepositoryView> {
+        let mut view = RepositoryView::finalize(
+            repo_path,
+            None,
+            Status::Error,
+            None,
+            None,
+            Vec::with_capacity(0),
+        )?;
+        view.error = Some(error.to_string());
+        Ok(view)
     }
 
-    /// Assemble a [`RepositoryView`] with metadata for a given repository.
+    /// Assemble a [`RepositoryView`] with metadata for a given repository. Optional details, such
+    /// as the `upstream`, `gone_upstream`, `incomparable_upstream`, `changes`, `operation`,
+    /// `stashes` and `branches` fields, are left empty and can be populated afterwards.
     pub fn finalize(
         path: &Path,
         branch: Option<String>,
@@ -119,9 +205,18 @@ impl RepositoryView {
             branch,
             status,
             parent,
+            nested_in: None,
             url,
+            upstream: None,
+            gone_upstream: None,
+            incomparable_upstream: None,
+            changes: None,
+            operation: None,
             email,
             submodules,
+            stashes: Vec::with_capacity(0),
+            branches: Vec::with_capacity(0),
+            error: None,
         })
     }
 
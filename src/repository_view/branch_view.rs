@@ -0,0 +1,61 @@
+//! This module contains the ability to gather information on all local branches for a given [`Repository`].
+
+use anyhow::{Result, anyhow};
+use git2::{BranchType, Repository};
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::status::{Status, Upstream};
+
+/// The view of a local branch within a [`Repository`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BranchView {
+    /// The name of the local branch.
+    pub name: String,
+    /// The comparison of the local branch against its remote branch. The value will be `None` if
+    /// the remote branch cannot be found.
+    pub upstream: Option<Upstream>,
+    /// The shorthand name of the upstream configured for the local branch if it could not be
+    /// found (e.g. the remote branch was deleted after merging). The value will be `None` if the
+    /// upstream was found or was never configured.
+    pub gone_upstream: Option<String>,
+    /// The shorthand name of the remote branch for the local branch if it was found, but could not
+    /// be compared against (e.g. history is missing in a shallow clone). The value will be `None`
+    /// otherwise.
+    pub incomparable_upstream: Option<String>,
+    /// The number of commits on the local branch that cannot be found on any remote branch.
+    pub unpushed: usize,
+}
+
+impl BranchView {
+    /// Generate a list of [`branch view(s)`](Self) for a given [`Repository`]. The remote name is
+    /// used to find remote branches for local branches without a configured upstream.
+    pub fn list(repo: &Repository, remote_name: Option<&str>) -> Result<Vec<Self>> {
+        let mut branches = Vec::new();
+        for maybe_branch in repo.branches(Some(BranchType::Local))? {
+            let (branch, _) = maybe_branch?;
+            let name = branch
+                .name()?
+                .ok_or(anyhow!("branch name is invalid UTF-8"))?
+                .to_string();
+            let reference = branch.get();
+
+            let (upstream, gone_upstream, incomparable_upstream) = match remote_name {
+                Some(remote_name) => {
+                    Status::find_upstream(repo, reference, remote_name)?.into_parts()
+                }
+                None => (None, None, None),
+            };
+            let unpushed = Status::count_unpushed(repo, reference)?;
+
+            branches.push(Self {
+                name,
+                upstream,
+                gone_upstream,
+                incomparable_upstream,
+                unpushed,
+            });
+        }
+        Ok(branches)
+    }
+}
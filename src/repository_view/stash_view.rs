@@ -0,0 +1,38 @@
+//! This module contains the ability to gather information on stash entries for a given [`Repository`].
+
+use anyhow::Result;
+use git2::{ErrorCode, Repository};
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The view of a stash entry within a [`Repository`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StashView {
+    /// The index of the stash entry, where zero is the most recent entry (i.e. "stash@{0}").
+    pub index: usize,
+    /// The message of the stash entry (e.g. "WIP on main: 1234567 message").
+    pub message: String,
+    /// The object id of the stash entry's commit.
+    pub oid: String,
+}
+
+impl StashView {
+    /// Generate a list of [`stash view(s)`](Self) for a given [`Repository`]. Iterating over stash
+    /// entries requires a mutable [`Repository`], but the repository is not modified.
+    pub fn list(repo: &mut Repository) -> Result<Vec<Self>> {
+        let mut stashes = Vec::new();
+        let result = repo.stash_foreach(|index, message, oid| {
+            stashes.push(Self {
+                index,
+                message: message.to_string(),
+                oid: oid.to_string(),
+            });
+            true
+        });
+        match result {
+            Ok(()) => Ok(stashes),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(Vec::with_capacity(0)),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
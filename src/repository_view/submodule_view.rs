@@ -22,7 +22,7 @@ impl SubmoduleView {
         for submodule in repo.submodules()? {
             match submodule.open() {
                 Ok(subrepo) => {
-                    let (status, _, _) = Status::find(&subrepo)?;
+                    let status = Status::find(&subrepo)?.status;
                     let name = submodule
                         .name()
                         .ok_or(anyhow!("submodule name is invalid UTF-8"))?;
@@ -1,18 +1,67 @@
 //! This module contains the [`crate::status::Status`] type.
 
+use std::fmt;
+
 use anyhow::Result;
-use git2::{ErrorCode, Reference, Remote, Repository, StatusOptions};
+use clap::ValueEnum;
+use git2::{ErrorCode, Reference, Remote, Repository, RepositoryState, StatusOptions, Statuses};
 use log::debug;
 use serde::{Deserialize, Serialize};
 
+/// The [`Status`] of a [`Repository`] alongside the information gathered while finding it.
+pub struct Findings<'repo> {
+    /// The [`Status`] of the working tree.
+    pub status: Status,
+    /// The reference for the current branch. The value will be `None` if the branch is unborn.
+    pub head: Option<Reference<'repo>>,
+    /// The remote used for the current branch. The value will be `None` if no remote is found.
+    pub remote: Option<Remote<'repo>>,
+    /// The comparison of the current branch against its remote branch. The value will be `None`
+    /// if the remote branch cannot be found.
+    pub upstream: Option<Upstream>,
+    /// The shorthand name of the upstream configured for the current branch if it could not be
+    /// found. The value will be `None` if the upstream was found or was never configured.
+    pub gone_upstream: Option<String>,
+    /// The shorthand name of the remote branch for the current branch if it was found, but could
+    /// not be compared against. The value will be `None` otherwise.
+    pub incomparable_upstream: Option<String>,
+    /// The number of entries for each kind of change. The value will be `None` unless the working
+    /// tree is unclean.
+    pub changes: Option<Changes>,
+}
+
+// The references and remotes from our dependency do not implement Debug, so their names are used.
+impl fmt::Debug for Findings<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Findings")
+            .field("status", &self.status)
+            .field("head", &self.head.as_ref().and_then(|h| h.shorthand()))
+            .field("remote", &self.remote.as_ref().and_then(|r| r.name()))
+            .field("upstream", &self.upstream)
+            .field("gone_upstream", &self.gone_upstream)
+            .field("incomparable_upstream", &self.incomparable_upstream)
+            .field("changes", &self.changes)
+            .finish()
+    }
+}
+
 /// A summarized interpretation of the status of a Git working tree.
 #[remain::sorted]
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, ValueEnum)]
 pub enum Status {
     /// Corresponds to a "bare" working tree.
     Bare,
+    /// Indicates that there is at least one commit on the remote that has not been pulled into a
+    /// clean working tree.
+    Behind,
     /// Corresponds to a "clean" working tree.
     Clean,
+    /// Indicates that the working tree is clean, but both the local and remote branches contain
+    /// commits that the other does not.
+    Diverged,
+    /// Provided if the repository could not be opened or its state could not be determined due to
+    /// an error.
+    Error,
     /// Corresponds to an "unclean" working tree.
     Unclean,
     /// Provided if the state of the working tree could neither be found nor determined.
@@ -21,21 +70,204 @@ pub enum Status {
     Unpushed,
 }
 
+/// The remote branch that the local branch was compared against, alongside the number of commits
+/// that the local branch is ahead of and behind it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Upstream {
+    /// The shorthand name of the remote branch (e.g. "origin/main").
+    pub name: String,
+    /// The number of commits found on the local branch, but not on the remote branch.
+    pub ahead: usize,
+    /// The number of commits found on the remote branch, but not on the local branch.
+    pub behind: usize,
+}
+
+/// The number of entries in the index and working tree for each kind of change. An entry can be
+/// counted for multiple kinds of changes (e.g. a staged deletion is counted as both `staged` and
+/// `deleted`), with the exception of untracked and conflicted entries.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Changes {
+    /// The number of entries with changes in the index.
+    pub staged: usize,
+    /// The number of entries modified in the working tree, but not in the index.
+    pub modified: usize,
+    /// The number of entries deleted in either the index or the working tree.
+    pub deleted: usize,
+    /// The number of entries renamed in the index.
+    pub renamed: usize,
+    /// The number of entries in the working tree that are not tracked.
+    pub untracked: usize,
+    /// The number of entries with merge conflicts.
+    pub conflicted: usize,
+}
+
+impl Changes {
+    fn new(statuses: &Statuses<'_>) -> Self {
+        let mut changes = Self::default();
+        for entry in statuses.iter() {
+            let status = entry.status();
+            if status.is_conflicted() {
+                changes.conflicted += 1;
+                continue;
+            }
+            if status.is_wt_new() {
+                changes.untracked += 1;
+                continue;
+            }
+            if status.intersects(
+                git2::Status::INDEX_NEW
+                    | git2::Status::INDEX_MODIFIED
+                    | git2::Status::INDEX_DELETED
+                    | git2::Status::INDEX_RENAMED
+                    | git2::Status::INDEX_TYPECHANGE,
+            ) {
+                changes.staged += 1;
+            }
+            if status.intersects(git2::Status::WT_MODIFIED | git2::Status::WT_TYPECHANGE) {
+                changes.modified += 1;
+            }
+            if status.intersects(git2::Status::INDEX_DELETED | git2::Status::WT_DELETED) {
+                changes.deleted += 1;
+            }
+            if status.is_index_renamed() {
+                changes.renamed += 1;
+            }
+        }
+        changes
+    }
+}
+
+/// An operation that was started, but has not been finished (e.g. a rebase stopped due to
+/// conflicts). Sequences of operations are reported as their individual operation.
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Operation {
+    /// Corresponds to "git am" (i.e. applying patches from a mailbox).
+    ApplyMailbox,
+    /// Corresponds to "git bisect".
+    Bisect,
+    /// Corresponds to "git cherry-pick".
+    CherryPick,
+    /// Corresponds to "git merge".
+    Merge,
+    /// Corresponds to "git rebase".
+    Rebase,
+    /// Corresponds to "git rebase --interactive".
+    RebaseInteractive,
+    /// Corresponds to "git revert".
+    Revert,
+}
+
+impl Operation {
+    /// Find the [`Operation`] in progress for a given [`Repository`], if any.
+    pub fn find(repo: &Repository) -> Option<Self> {
+        match repo.state() {
+            RepositoryState::Clean => None,
+            RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                Some(Self::ApplyMailbox)
+            }
+            RepositoryState::Bisect => Some(Self::Bisect),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some(Self::CherryPick)
+            }
+            RepositoryState::Merge => Some(Self::Merge),
+            RepositoryState::Rebase | RepositoryState::RebaseMerge => Some(Self::Rebase),
+            RepositoryState::RebaseInteractive => Some(Self::RebaseInteractive),
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some(Self::Revert),
+        }
+    }
+
+    /// Converts the enum into a borrowed, static `str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ApplyMailbox => "apply-mailbox",
+            Self::Bisect => "bisect",
+            Self::CherryPick => "cherry-pick",
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+            Self::RebaseInteractive => "rebase-interactive",
+            Self::Revert => "revert",
+        }
+    }
+}
+
+/// The result of comparing a local branch against its remote branch.
+#[derive(Debug)]
+pub enum Comparison {
+    /// The remote branch was found and compared against.
+    Compared(Upstream),
+    /// An upstream was configured, but its reference could not be found (e.g. the remote branch
+    /// was deleted after merging). Contains the shorthand name of the configured upstream.
+    Gone(String),
+    /// The remote branch was found, but could not be compared against (e.g. history is missing
+    /// in a shallow clone). Contains the shorthand name of the remote branch.
+    Incomparable(String),
+    /// The remote branch could not be found and no upstream was configured.
+    Missing,
+}
+
+impl Comparison {
+    /// Split the comparison into the [`Upstream`] compared against, the name of the gone upstream
+    /// and the name of the incomparable upstream. At most one of them will be `Some`.
+    pub fn into_parts(self) -> (Option<Upstream>, Option<String>, Option<String>) {
+        match self {
+            Self::Compared(upstream) => (Some(upstream), None, None),
+            Self::Gone(name) => (None, Some(name), None),
+            Self::Incomparable(name) => (None, None, Some(name)),
+            Self::Missing => (None, None, None),
+        }
+    }
+}
+
+/// The result of looking for the reference that a local branch will be compared against.
+enum UpstreamReference<'repo> {
+    /// The reference was found.
+    Found(Reference<'repo>),
+    /// An upstream was configured, but its reference could not be found (e.g. the remote branch
+    /// was deleted after merging). Contains the shorthand name of the configured upstream.
+    Gone(String),
+    /// The reference could not be found and no upstream was configured.
+    Missing,
+}
+
 impl Status {
     /// Converts the enum into a borrowed, static `str`.
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Bare => "bare",
+            Self::Behind => "behind",
             Self::Clean => "clean",
+            Self::Diverged => "diverged",
+            Self::Error => "error",
             Self::Unclean => "unclean",
             Self::Unknown => "unknown",
             Self::Unpushed => "unpushed",
         }
     }
 
-    /// Find the [`Status`] for a given [`Repository`]. The
-    /// [`head`](Option<git2::Reference>) and [`remote`](Option<git2::Remote>) are also returned.
-    pub fn find(repo: &Repository) -> Result<(Status, Option<Reference<'_>>, Option<Remote<'_>>)> {
+    /// Converts the enum into the bit used to build the exit code when checking repositories.
+    /// The bits for all failing statuses found are combined, which means that the resulting exit
+    /// code describes every failing status found. The two lowest bits are never used since exit
+    /// codes 1 and 2 are reserved for general failures and usage errors respectively.
+    /// [`Status::Diverged`] shares its bits with [`Status::Unpushed`] and [`Status::Behind`] since
+    /// it implies both, and [`Status::Bare`] shares its bit with [`Status::Unknown`] since neither
+    /// has a working tree that can be compared.
+    pub fn as_exit_code_bits(&self) -> u8 {
+        match self {
+            Self::Unclean => 4,
+            Self::Unpushed => 8,
+            Self::Behind => 16,
+            Self::Diverged => 24,
+            Self::Error => 32,
+            Self::Unknown => 64,
+            Self::Bare => 64,
+            Self::Clean => 128,
+        }
+    }
+
+    /// Find the [`Status`] for a given [`Repository`], alongside the rest of the [`Findings`]
+    /// gathered while finding it.
+    pub fn find(repo: &Repository) -> Result<Findings<'_>> {
         let head = match repo.head() {
             Ok(head) => Some(head),
             Err(ref e)
@@ -53,61 +285,184 @@ impl Status {
             Err(e) => return Err(e.into()),
         };
 
+        // The comparison against the remote branch is performed regardless of the state of the
+        // working tree since the counts are useful for every status.
+        let comparison = match (&head, &remote_name) {
+            (Some(head), Some(remote_name)) => Self::find_upstream(repo, head, remote_name)?,
+            _ => Comparison::Missing,
+        };
+
         // We'll include all untracked files and directories in the status options.
         let mut opts = StatusOptions::new();
         opts.include_untracked(true).recurse_untracked_dirs(true);
 
+        // Renames are only detected if requested, which allows us to count them. Detecting renames
+        // in the working tree would compare every deleted file against every untracked file, so we
+        // only detect renames that have been staged.
+        opts.renames_head_to_index(true);
+
         // If "head" is "None" and statuses are empty, then the repository_view must be clean because there
-        // are no commits to push.
-        let status = match repo.statuses(Some(&mut opts)) {
-            Ok(v) if v.is_empty() => match &head {
-                Some(head) => match remote_name {
-                    Some(remote_name) => match Self::is_unpushed(repo, head, &remote_name)? {
-                        true => Status::Unpushed,
-                        false => Status::Clean,
+        // are no commits to push. If the configured upstream is gone, the local branch is only
+        // unpushed if it contains commits that cannot be found on any remote branch. If the remote
+        // branch was found, but could not be compared against, we do not assume that the local
+        // branch is unpushed. Otherwise, if the remote branch could not be found, we assume that
+        // the local branch is unpushed.
+        let (status, changes) = match repo.statuses(Some(&mut opts)) {
+            Ok(v) if v.is_empty() => {
+                let status = match (&head, &remote_name) {
+                    (Some(head), Some(_)) => match &comparison {
+                        Comparison::Compared(upstream) => {
+                            match (upstream.ahead > 0, upstream.behind > 0) {
+                                (true, true) => Status::Diverged,
+                                (true, false) => Status::Unpushed,
+                                (false, true) => Status::Behind,
+                                (false, false) => Status::Clean,
+                            }
+                        }
+                        Comparison::Gone(_) => match Self::count_unpushed(repo, head)? {
+                            0 => Status::Clean,
+                            _ => Status::Unpushed,
+                        },
+                        Comparison::Incomparable(_) => Status::Clean,
+                        Comparison::Missing => Status::Unpushed,
                     },
-                    None => Status::Clean,
-                },
-                None => Status::Clean,
-            },
-            Ok(_) => Status::Unclean,
-            Err(e) if e.code() == ErrorCode::BareRepo => Status::Bare,
+                    _ => Status::Clean,
+                };
+                (status, None)
+            }
+            Ok(v) => (Status::Unclean, Some(Changes::new(&v))),
+            Err(e) if e.code() == ErrorCode::BareRepo => (Status::Bare, None),
             Err(e) => return Err(e.into()),
         };
 
-        Ok((status, head, remote))
+        let (upstream, gone_upstream, incomparable_upstream) = comparison.into_parts();
+        Ok(Findings {
+            status,
+            head,
+            remote,
+            upstream,
+            gone_upstream,
+            incomparable_upstream,
+            changes,
+        })
     }
 
-    // Checks if local commit(s) on the current branch have not yet been pushed to the remote.
-    fn is_unpushed(
+    /// Compare the given local branch against its remote branch, if the remote branch can be
+    /// found.
+    pub fn find_upstream(
         repo: &Repository,
         head: &Reference<'_>,
         remote_name: &str,
-    ) -> Result<bool, git2::Error> {
+    ) -> Result<Comparison, git2::Error> {
         let local_head = head.peel_to_commit()?;
-        let remote = format!(
-            "{}/{}",
-            remote_name,
-            match head.shorthand() {
-                Some(v) => v,
-                None => {
-                    debug!("assuming unpushed; could not determine shorthand for head");
-                    return Ok(true);
+        let reference = match Self::find_upstream_reference(repo, head, remote_name)? {
+            UpstreamReference::Found(reference) => reference,
+            UpstreamReference::Gone(name) => return Ok(Comparison::Gone(name)),
+            UpstreamReference::Missing => return Ok(Comparison::Missing),
+        };
+        let name = match reference.shorthand() {
+            Some(v) => v.to_string(),
+            None => {
+                debug!("assuming unpushed; could not determine shorthand for remote reference");
+                return Ok(Comparison::Missing);
+            }
+        };
+        let remote_head = reference.peel_to_commit()?;
+        match repo.graph_ahead_behind(local_head.id(), remote_head.id()) {
+            Ok((ahead, behind)) => Ok(Comparison::Compared(Upstream {
+                name,
+                ahead,
+                behind,
+            })),
+            Err(e) => {
+                debug!(
+                    "assuming not unpushed; could not compare against remote reference (ignored error: {e})"
+                );
+                Ok(Comparison::Incomparable(name))
+            }
+        }
+    }
+
+    /// Count the commits on the given local branch that cannot be found on any remote branch.
+    pub fn count_unpushed(repo: &Repository, head: &Reference<'_>) -> Result<usize, git2::Error> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head.peel_to_commit()?.id())?;
+        revwalk.hide_glob("refs/remotes/*")?;
+        Ok(revwalk.count())
+    }
+
+    // Finds the reference that the current branch will be compared against. In order, we look for
+    // the upstream configured via "branch.<name>.remote" and "branch.<name>.merge" (i.e. what
+    // "git status" uses), the push destination configured via "branch.<name>.pushRemote" or
+    // "remote.pushDefault" (i.e. "@{push}") and, finally, "<remote>/<branch>".
+    fn find_upstream_reference<'repo>(
+        repo: &'repo Repository,
+        head: &Reference<'_>,
+        remote_name: &str,
+    ) -> Result<UpstreamReference<'repo>, git2::Error> {
+        let Some(shorthand) = head.shorthand() else {
+            debug!("assuming unpushed; could not determine shorthand for head");
+            return Ok(UpstreamReference::Missing);
+        };
+
+        if head.is_branch()
+            && let Some(head_name) = head.name()
+        {
+            match repo.branch_upstream_name(head_name) {
+                Ok(upstream_name) => match upstream_name.as_str() {
+                    Some(upstream_name) => match repo.find_reference(upstream_name) {
+                        Ok(reference) => return Ok(UpstreamReference::Found(reference)),
+                        Err(e) if e.code() == ErrorCode::NotFound => {
+                            debug!("configured upstream is gone: {upstream_name}");
+                            let name = upstream_name
+                                .strip_prefix("refs/remotes/")
+                                .unwrap_or(upstream_name);
+                            return Ok(UpstreamReference::Gone(name.to_string()));
+                        }
+                        Err(e) => return Err(e),
+                    },
+                    None => debug!("ignoring configured upstream; name is invalid UTF-8"),
+                },
+                Err(e) if e.code() == ErrorCode::NotFound => {}
+                Err(e) => debug!("ignoring configured upstream (ignored error: {e})"),
+            }
+
+            if let Some(push_remote) = Self::find_push_remote(repo, shorthand)? {
+                match repo.find_reference(&format!("refs/remotes/{push_remote}/{shorthand}")) {
+                    Ok(reference) => return Ok(UpstreamReference::Found(reference)),
+                    Err(e) if e.code() == ErrorCode::NotFound => {
+                        debug!("could not find push destination on remote: {push_remote}");
+                    }
+                    Err(e) => return Err(e),
                 }
             }
-        );
-        let remote_head = match repo.resolve_reference_from_short_name(&remote) {
-            Ok(reference) => reference.peel_to_commit()?,
+        }
+
+        match repo.resolve_reference_from_short_name(&format!("{remote_name}/{shorthand}")) {
+            Ok(reference) => Ok(UpstreamReference::Found(reference)),
             Err(e) => {
                 debug!(
                     "assuming unpushed; could not resolve remote reference from short name (ignored error: {e})"
                 );
-                return Ok(true);
+                Ok(UpstreamReference::Missing)
             }
-        };
-        Ok(
-            matches!(repo.graph_ahead_behind(local_head.id(), remote_head.id()), Ok(number_unique_commits) if number_unique_commits.0 > 0),
-        )
+        }
+    }
+
+    // Finds the remote that the branch is pushed to, if one has been explicitly configured.
+    fn find_push_remote(repo: &Repository, branch: &str) -> Result<Option<String>, git2::Error> {
+        let config = repo.config()?;
+        for key in [
+            format!("branch.{branch}.pushRemote"),
+            "remote.pushDefault".to_string(),
+        ] {
+            match config.get_string(&key) {
+                Ok(value) => return Ok(Some(value)),
+                Err(e) if e.code() == ErrorCode::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(None)
     }
 
     fn choose_remote_greedily(